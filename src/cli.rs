@@ -1,8 +1,23 @@
 /// This module handles Command Line Interface (CLI) related logic.
+use crate::config::RequestOverrides;
 use crate::error::{Error, Result};
-use crate::storage::{OutputFormat, StorageClient};
-use crate::utils::confirm_deletion;
-use clap::{Parser, Subcommand};
+use crate::storage::constants::{DEFAULT_JOBS, DEFAULT_PART_CONCURRENCY, MIN_PART_SIZE};
+use crate::storage::location::{ClientResolver, StorageTarget};
+use crate::storage::{
+    Action, ChecksumAlgo, Comparison, DryRun, EntryKind, Failure, FindPredicates, OutputFormat,
+    PathFilter, RateLimiter, Reporter, StorageClient, TransferOptions, parse_size,
+};
+use crate::utils::{confirm_deletion, confirm_large_file};
+use clap::{CommandFactory, Parser, Subcommand};
+use globset::{GlobBuilder, GlobMatcher};
+use render::{ProgressPrinter, Renderer};
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+mod render;
 
 /// Custom parser to validate that a path is not empty.
 fn parse_validated_path(path_str: &str) -> Result<String> {
@@ -15,19 +30,257 @@ fn parse_validated_path(path_str: &str) -> Result<String> {
     }
 }
 
+/// Parse a count that must be at least one, reporting `name` on failure.
+fn parse_positive(name: &str, value: &str) -> Result<usize> {
+    match value.trim().parse::<usize>() {
+        Ok(count) if count >= 1 => Ok(count),
+        _ => Err(Error::InvalidArgument {
+            name: name.to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+/// Custom parser to validate that a job count is at least one.
+fn parse_jobs(value: &str) -> Result<usize> {
+    parse_positive("--jobs", value)
+}
+
+/// Custom parser to validate that a request timeout is at least one second.
+fn parse_timeout(value: &str) -> Result<u64> {
+    parse_positive("--timeout", value).map(|secs| secs as u64)
+}
+
+/// Custom parser to validate that an IO timeout is at least one second.
+fn parse_io_timeout(value: &str) -> Result<u64> {
+    parse_positive("--io-timeout", value).map(|secs| secs as u64)
+}
+
+/// Custom parser for a human-readable bandwidth limit of at least one byte per second.
+fn parse_limit_rate(value: &str) -> Result<u64> {
+    match parse_size(value) {
+        Some(rate) if rate >= 1 => Ok(rate),
+        _ => Err(Error::InvalidArgument {
+            name: "--limit-rate".to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+/// Custom parser to validate that a part concurrency is at least one.
+fn parse_concurrency(value: &str) -> Result<usize> {
+    parse_positive("--concurrency", value)
+}
+
+/// Custom parser for checksum algorithm names.
+fn parse_checksum_algo(value: &str) -> Result<ChecksumAlgo> {
+    value.parse()
+}
+
+/// Custom parser for output format names.
+fn parse_output_format(value: &str) -> Result<OutputFormat> {
+    value.parse()
+}
+
+/// Custom parser for human-readable part sizes no smaller than `MIN_PART_SIZE`.
+fn parse_part_size(value: &str) -> Result<u64> {
+    match parse_size(value) {
+        Some(size) if size >= MIN_PART_SIZE => Ok(size),
+        _ => Err(Error::InvalidArgument {
+            name: "--part-size".to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+/// Compile a `find` name glob, optionally ignoring case.
+fn name_glob(name: &str, value: &str, case_insensitive: bool) -> Result<GlobMatcher> {
+    GlobBuilder::new(value)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map(|glob| glob.compile_matcher())
+        .map_err(|_| Error::InvalidArgument {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+}
+
+/// Custom parser for `find -name` globs.
+fn parse_name_glob(value: &str) -> Result<GlobMatcher> {
+    name_glob("-name", value, false)
+}
+
+/// Custom parser for `find -iname` globs.
+fn parse_iname_glob(value: &str) -> Result<GlobMatcher> {
+    name_glob("-iname", value, true)
+}
+
+/// Custom parser for `find -size`: `+N`, `-N` or `N`, with an optional K/M/G/T unit.
+fn parse_size_comparison(value: &str) -> Result<Comparison> {
+    Comparison::parse(value, parse_size).ok_or_else(|| Error::InvalidArgument {
+        name: "-size".to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// Custom parser for `find -mtime`: `+N`, `-N` or `N` days.
+fn parse_mtime_comparison(value: &str) -> Result<Comparison> {
+    Comparison::parse(value, |days| days.parse().ok()).ok_or_else(|| Error::InvalidArgument {
+        name: "-mtime".to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// Custom parser for `find -type`: `f` or `d`.
+fn parse_entry_kind(value: &str) -> Result<EntryKind> {
+    match value {
+        "f" => Ok(EntryKind::File),
+        "d" => Ok(EntryKind::Dir),
+        _ => Err(Error::InvalidArgument {
+            name: "-type".to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+/// `find` predicates and actions that are written with a single dash, as in HDFS and POSIX find.
+const FIND_PREDICATES: &[&str] = &[
+    "-name", "-iname", "-size", "-mtime", "-type", "-print0", "-delete",
+];
+
+/// Rewrite the single-dash predicates of `find` (`-name`, `-size`, ...) into the long
+/// options clap parses. Arguments of other commands are left untouched.
+pub fn normalize_args(args: impl IntoIterator<Item = OsString>) -> Vec<OsString> {
+    let mut args: Vec<OsString> = args.into_iter().collect();
+    // Global options taking a separate value, whose value must be skipped to find the command.
+    let command = Args::command();
+    let takes_value: Vec<String> = command
+        .get_arguments()
+        .filter(|arg| arg.get_action().takes_values())
+        .flat_map(|arg| {
+            let long = arg.get_long().map(|long| format!("--{long}"));
+            let short = arg.get_short().map(|short| format!("-{short}"));
+            long.into_iter().chain(short)
+        })
+        .collect();
+    let mut index = 1;
+    while let Some(arg) = args.get(index) {
+        if arg
+            .to_str()
+            .is_some_and(|a| takes_value.iter().any(|o| o == a))
+        {
+            index += 2;
+        } else if arg.to_string_lossy().starts_with('-') {
+            index += 1;
+        } else {
+            break;
+        }
+    }
+    if args.get(index).is_some_and(|command| command == "find") {
+        for arg in &mut args[index + 1..] {
+            if arg.to_str().is_some_and(|a| FIND_PREDICATES.contains(&a)) {
+                let mut long = OsString::from("-");
+                long.push(&*arg);
+                *arg = long;
+            }
+        }
+    }
+    args
+}
+
+/// Read glob patterns from a file, one per line, skipping blank lines and `#` comments.
+fn read_patterns(path: &Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path).map_err(|_| Error::InvalidArgument {
+        name: "--exclude-from".to_string(),
+        value: path.display().to_string(),
+    })?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
 /// Storify - A unified tool for managing object storage with HDFS-like interface
 #[derive(Parser, Debug)]
 #[command(
     version = "0.1.0",
     author = "WangErxi",
     about = "A unified tool for managing object storage (OSS, S3, etc.)",
-    after_help = "Enjoy the unified experience!"
+    after_help = "Remote paths may be bare keys, 's3://bucket/key', 'oss://bucket/key', \
+                  'cos://bucket/key', 'fs:///local/path' or 'profile-name:/key'.\n\n\
+                  Enjoy the unified experience!"
 )]
 pub struct Args {
+    /// Named profile from the config file to use
+    #[arg(long, global = true, env = "STORIFY_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Path to the config file (default: ~/.config/storify/config.toml)
+    #[arg(long, global = true, env = "STORIFY_CONFIG", value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Treat remote paths literally instead of expanding `*`, `?`, `[...]` and `**`
+    #[arg(long, global = true)]
+    pub no_glob: bool,
+
+    /// Print what mutating commands would write, move or delete without changing anything
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    /// Output format: human, raw, json or jsonl
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "human", value_parser = parse_output_format)]
+    pub output: OutputFormat,
+
+    /// Retries of requests failing with a temporary error, 0 to disable [default: 3]
+    #[arg(long, global = true, value_name = "N", env = "STORIFY_RETRIES")]
+    pub retries: Option<usize>,
+
+    /// Timeout in seconds of requests such as stat, list or delete [default: 60]
+    #[arg(long, global = true, value_name = "SECS", env = "STORIFY_TIMEOUT", value_parser = parse_timeout)]
+    pub timeout: Option<u64>,
+
+    /// Timeout in seconds of every single read or write of a transfer [default: 30]
+    #[arg(long, global = true, value_name = "SECS", env = "STORIFY_IO_TIMEOUT", value_parser = parse_io_timeout)]
+    pub io_timeout: Option<u64>,
+
+    /// Cap the combined throughput of put, get, cp, mv, cat and sync, in bytes per second (e.g. 20M)
+    #[arg(long, global = true, value_name = "RATE", env = "STORIFY_LIMIT_RATE", value_parser = parse_limit_rate)]
+    pub limit_rate: Option<u64>,
+
+    /// Keep going after a file fails in put, get, cp, mv and sync, reporting every failure at the end
+    #[arg(long, global = true)]
+    pub continue_on_error: bool,
+
+    /// Write the paths that failed, with their error kind, as JSON to FILE
+    #[arg(long, global = true, value_name = "FILE")]
+    pub failures_file: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+impl Args {
+    /// Retry and timeout settings given on the command line.
+    pub fn request_overrides(&self) -> RequestOverrides {
+        RequestOverrides {
+            retries: self.retries,
+            timeout: self.timeout,
+            io_timeout: self.io_timeout,
+        }
+    }
+
+    /// Empty the `--failures-file`, so that it never lists the failures of an earlier run,
+    /// even when this one fails before reaching any path.
+    pub fn clear_failures_file(&self) -> Result<()> {
+        if let Some(path) = &self.failures_file {
+            fs::write(path, "[]")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// List directory contents
@@ -40,16 +293,26 @@ pub enum Commands {
     Put(PutArgs),
     /// Remove files/directories from remote storage
     Rm(RmArgs),
-    /// Copy files/directories from remote to remote
+    /// Copy files/directories from remote to remote (across providers too)
     Cp(CpArgs),
-    /// Move files/directories from remote to remote
+    /// Move files/directories from remote to remote (across providers too)
     Mv(MvArgs),
     /// Create directories in remote storage
     Mkdir(MkdirArgs),
+    /// Incrementally synchronize a destination tree with a source tree
+    Sync(SyncArgs),
+    /// Compare two directory trees and list added, removed and modified files
+    Diff(DiffArgs),
     /// Display object metadata
     Stat(StatArgs),
     /// Display file contents
     Cat(CatArgs),
+    /// Compute content checksums of files or directory prefixes
+    Checksum(ChecksumArgs),
+    /// Compare a local directory with a remote prefix
+    Verify(VerifyArgs),
+    /// Find files and directories by name, size, age and type
+    Find(FindArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -65,6 +328,8 @@ pub struct LsArgs {
     /// Process directories recursively
     #[arg(short = 'R', long)]
     pub recursive: bool,
+    #[command(flatten)]
+    pub filter: FilterArgs,
 }
 
 #[derive(Parser, Debug)]
@@ -76,6 +341,16 @@ pub struct GetArgs {
     /// The local path to download to
     #[arg(value_name = "LOCAL", value_parser = parse_validated_path)]
     pub local: String,
+
+    /// Continue an interrupted transfer instead of starting over
+    #[arg(long)]
+    pub resume: bool,
+
+    #[command(flatten)]
+    pub parts: PartArgs,
+
+    #[command(flatten)]
+    pub transfer: TransferArgs,
 }
 
 #[derive(Parser, Debug)]
@@ -87,6 +362,8 @@ pub struct DuArgs {
     /// Show summary only
     #[arg(short = 's', long)]
     pub summary: bool,
+    #[command(flatten)]
+    pub filter: FilterArgs,
 }
 
 #[derive(Parser, Debug)]
@@ -102,6 +379,16 @@ pub struct PutArgs {
     /// Process directories recursively
     #[arg(short = 'R', long)]
     pub recursive: bool,
+
+    /// Continue an interrupted transfer instead of starting over
+    #[arg(long)]
+    pub resume: bool,
+
+    #[command(flatten)]
+    pub parts: PartArgs,
+
+    #[command(flatten)]
+    pub transfer: TransferArgs,
 }
 
 #[derive(Parser, Debug)]
@@ -117,6 +404,8 @@ pub struct RmArgs {
     /// Force deletion without confirmation
     #[arg(short = 'f', long)]
     pub force: bool,
+    #[command(flatten)]
+    pub filter: FilterArgs,
 }
 
 #[derive(Parser, Debug)]
@@ -128,6 +417,13 @@ pub struct CpArgs {
     /// The remote path to copy to
     #[arg(value_name = "DEST", value_parser = parse_validated_path)]
     pub dest_path: String,
+
+    /// Always stream data through the client instead of using server-side copy
+    #[arg(long)]
+    pub no_server_side: bool,
+
+    #[command(flatten)]
+    pub transfer: TransferArgs,
 }
 
 #[derive(Parser, Debug)]
@@ -139,6 +435,129 @@ pub struct MvArgs {
     /// The remote path to move to
     #[arg(value_name = "DEST", value_parser = parse_validated_path)]
     pub dest_path: String,
+
+    /// Always stream data through the client instead of using server-side rename/copy
+    #[arg(long)]
+    pub no_server_side: bool,
+
+    #[command(flatten)]
+    pub transfer: TransferArgs,
+}
+
+/// Options shared by commands that transfer file contents.
+#[derive(Parser, Debug)]
+pub struct TransferArgs {
+    /// Number of files to transfer concurrently in recursive operations
+    #[arg(short = 'j', long, value_name = "N", default_value_t = DEFAULT_JOBS, value_parser = parse_jobs)]
+    pub jobs: usize,
+
+    /// Verify checksums after each file is transferred
+    #[arg(long)]
+    pub verify: bool,
+
+    #[command(flatten)]
+    pub filter: FilterArgs,
+}
+
+impl TransferArgs {
+    fn options(&self) -> Result<TransferOptions> {
+        Ok(TransferOptions {
+            jobs: self.jobs,
+            verify: self.verify,
+            filter: self.filter.filter()?,
+            ..TransferOptions::default()
+        })
+    }
+}
+
+/// Glob patterns selecting which files recursive operations act on.
+///
+/// Patterns match paths relative to the walked root; a pattern without `/` matches
+/// file names at any depth. Excludes take precedence over includes.
+#[derive(Parser, Debug)]
+pub struct FilterArgs {
+    /// Only act on files matching PATTERN (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    pub include: Vec<String>,
+
+    /// Skip files matching PATTERN (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+
+    /// Read exclude patterns from FILE, one per line (repeatable)
+    #[arg(long, value_name = "FILE")]
+    pub exclude_from: Vec<PathBuf>,
+}
+
+impl FilterArgs {
+    fn filter(&self) -> Result<PathFilter> {
+        let mut exclude = self.exclude.clone();
+        for file in &self.exclude_from {
+            exclude.extend(read_patterns(file)?);
+        }
+        PathFilter::new(&self.include, &exclude)
+    }
+}
+
+/// Options controlling how a single large file is split into concurrently transferred parts.
+#[derive(Parser, Debug)]
+pub struct PartArgs {
+    /// Part size for multipart uploads and ranged downloads, e.g. 8M or 64MiB
+    /// (default: chosen by file size)
+    #[arg(long, value_name = "SIZE", value_parser = parse_part_size)]
+    pub part_size: Option<u64>,
+
+    /// Number of parts of a single file transferred concurrently
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_PART_CONCURRENCY, value_parser = parse_concurrency)]
+    pub concurrency: usize,
+}
+
+impl PartArgs {
+    fn apply(&self, options: TransferOptions) -> TransferOptions {
+        TransferOptions {
+            part_size: self.part_size,
+            concurrency: self.concurrency,
+            ..options
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct SyncArgs {
+    /// The source directory (use fs:///path for the local filesystem)
+    #[arg(value_name = "SRC", value_parser = parse_validated_path)]
+    pub src_path: String,
+
+    /// The destination directory (use fs:///path for the local filesystem)
+    #[arg(value_name = "DEST", value_parser = parse_validated_path)]
+    pub dest_path: String,
+
+    /// Delete destination files that do not exist in the source
+    #[arg(long)]
+    pub delete: bool,
+    #[command(flatten)]
+    pub filter: FilterArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct DiffArgs {
+    /// The first directory (use fs:///path for the local filesystem)
+    #[arg(value_name = "A", value_parser = parse_validated_path)]
+    pub old_path: String,
+
+    /// The second directory (use fs:///path for the local filesystem)
+    #[arg(value_name = "B", value_parser = parse_validated_path)]
+    pub new_path: String,
+
+    /// Only print the number of added, removed, modified and unchanged files
+    #[arg(long)]
+    pub stat: bool,
+
+    /// Output as JSON (machine-readable)
+    #[arg(long)]
+    pub json: bool,
+    #[command(flatten)]
+    pub filter: FilterArgs,
 }
 
 #[derive(Parser, Debug)]
@@ -166,6 +585,130 @@ pub struct CatArgs {
     pub size_limit_mb: u64,
 }
 
+#[derive(Parser, Debug)]
+pub struct ChecksumArgs {
+    /// Files or directory prefixes to checksum
+    #[arg(value_name = "PATH", required = true, value_parser = parse_validated_path)]
+    pub paths: Vec<String>,
+
+    /// Hash algorithm: md5, sha256 or crc32c
+    #[arg(long, value_name = "ALGO", default_value = "md5", value_parser = parse_checksum_algo)]
+    pub algo: ChecksumAlgo,
+
+    /// Print `sha256sum`-compatible lines (HASH  PATH) without ETags
+    #[arg(long)]
+    pub manifest: bool,
+    #[command(flatten)]
+    pub filter: FilterArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct VerifyArgs {
+    /// Local directory expected to be mirrored
+    #[arg(value_name = "LOCAL", value_parser = parse_validated_path)]
+    pub local: String,
+
+    /// Remote directory prefix to compare against
+    #[arg(value_name = "REMOTE", value_parser = parse_validated_path)]
+    pub remote: String,
+
+    /// Also compare MD5 content hashes of files with equal sizes
+    #[arg(long)]
+    pub checksum: bool,
+
+    /// Output the report as JSON (machine-readable)
+    #[arg(long)]
+    pub json: bool,
+    #[command(flatten)]
+    pub filter: FilterArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct FindArgs {
+    /// The directory to search
+    #[arg(value_name = "PATH", value_parser = parse_validated_path)]
+    pub path: String,
+
+    /// Entry name matches the glob PATTERN
+    #[arg(long, value_name = "PATTERN", value_parser = parse_name_glob)]
+    pub name: Option<GlobMatcher>,
+
+    /// Like -name, but case-insensitive
+    #[arg(long, value_name = "PATTERN", value_parser = parse_iname_glob)]
+    pub iname: Option<GlobMatcher>,
+
+    /// Size is more (+N), less (-N) or exactly (N) bytes; K, M, G and T units allowed
+    #[arg(long, value_name = "[+-]N", allow_hyphen_values = true, value_parser = parse_size_comparison)]
+    pub size: Option<Comparison>,
+
+    /// Last modified more (+N), less (-N) or exactly (N) days ago
+    #[arg(long, value_name = "[+-]N", allow_hyphen_values = true, value_parser = parse_mtime_comparison)]
+    pub mtime: Option<Comparison>,
+
+    /// Entry type: f (file) or d (directory)
+    #[arg(long = "type", value_name = "f|d", value_parser = parse_entry_kind)]
+    pub kind: Option<EntryKind>,
+
+    /// Separate printed paths with NUL instead of newlines (for xargs -0)
+    #[arg(long)]
+    pub print0: bool,
+
+    /// Delete the found entries instead of printing them
+    #[arg(long)]
+    pub delete: bool,
+
+    /// Delete without confirmation
+    #[arg(short = 'f', long, requires = "delete")]
+    pub force: bool,
+}
+
+impl FindArgs {
+    fn predicates(&self) -> FindPredicates {
+        FindPredicates {
+            name: self.name.clone(),
+            iname: self.iname.clone(),
+            size: self.size,
+            mtime: self.mtime,
+            kind: self.kind,
+        }
+    }
+}
+
+impl Commands {
+    /// Whether the command writes, moves or deletes anything, i.e. is affected by `--dry-run`.
+    fn is_mutating(&self) -> bool {
+        match self {
+            Self::Get(_)
+            | Self::Put(_)
+            | Self::Rm(_)
+            | Self::Cp(_)
+            | Self::Mv(_)
+            | Self::Sync(_)
+            | Self::Mkdir(_) => true,
+            Self::Find(find_args) => find_args.delete,
+            _ => false,
+        }
+    }
+
+    /// Whether the command renders its results as items through `Renderer`, so that
+    /// `--output json` prints an array even when there are none.
+    fn renders_items(&self) -> bool {
+        matches!(
+            self,
+            Self::Ls(_)
+                | Self::Du(_)
+                | Self::Get(_)
+                | Self::Put(_)
+                | Self::Rm(_)
+                | Self::Cp(_)
+                | Self::Mv(_)
+                | Self::Sync(_)
+                | Self::Mkdir(_)
+                | Self::Checksum(_)
+        )
+    }
+}
+
 #[derive(Parser, Debug)]
 pub struct StatArgs {
     /// The path to stat
@@ -181,54 +724,295 @@ pub struct StatArgs {
     pub raw: bool,
 }
 
-pub async fn run(args: Args, client: StorageClient) -> Result<()> {
-    match args.command {
+pub async fn run(args: Args, resolver: ClientResolver) -> Result<()> {
+    let renderer = match &args.command {
+        Commands::Ls(ls_args) => Renderer::new(args.output).long_listing(ls_args.long),
+        Commands::Checksum(checksum_args) => {
+            Renderer::new(args.output).manifest(checksum_args.manifest)
+        }
+        _ => Renderer::new(args.output),
+    };
+    let renderer = Arc::new(renderer);
+    let dry_run = args.dry_run.then(DryRun::default);
+    let rate_limit = args.limit_rate.map(RateLimiter::new);
+    let mutating = args.command.is_mutating();
+    let renders_items = args.command.renders_items();
+
+    let resolver = resolver.with_progress_observer(Arc::new(ProgressPrinter::new(args.output)));
+    let result = execute(
+        args.command,
+        resolver,
+        !args.no_glob,
+        &renderer,
+        dry_run.as_ref(),
+        rate_limit.as_ref(),
+        args.continue_on_error,
+    )
+    .await;
+    if let Some(plan) = &dry_run
+        && mutating
+        && result.is_ok()
+    {
+        renderer.dry_run_summary(plan);
+    }
+    // Items rendered before a failure are still printed, including per-item errors.
+    renderer.finish(renders_items)?;
+    // A command failing as a whole has no per-path failures to report.
+    let failures = match &result {
+        Ok(()) => Some(&[][..]),
+        Err(e) => e.failures(),
+    };
+    if let Some(failures) = failures {
+        renderer.failure_summary(failures);
+        if let Some(path) = &args.failures_file {
+            fs::write(path, serde_json::to_string_pretty(failures)?)?;
+        }
+    }
+    result
+}
+
+async fn execute(
+    command: Commands,
+    mut resolver: ClientResolver,
+    glob: bool,
+    renderer: &Arc<Renderer>,
+    dry_run: Option<&DryRun>,
+    rate_limit: Option<&RateLimiter>,
+    continue_on_error: bool,
+) -> Result<()> {
+    let reporter = Reporter::new(renderer.clone());
+    match command {
         Commands::Ls(ls_args) => {
-            client
-                .list_directory(&ls_args.path, ls_args.long, ls_args.recursive)
-                .await?;
+            let filter = ls_args.filter.filter()?;
+            let (client, paths) = resolve_all(&mut resolver, &ls_args.path, glob).await?;
+            for path in &paths {
+                client
+                    .list_directory(path, ls_args.recursive, &filter, &reporter)
+                    .await?;
+            }
         }
         Commands::Get(get_args) => {
-            client
-                .download_files(&get_args.remote, &get_args.local)
-                .await?;
+            let options = TransferOptions {
+                resume: get_args.resume,
+                dry_run: dry_run.cloned(),
+                reporter,
+                rate_limit: rate_limit.cloned(),
+                continue_on_error,
+                ..get_args.parts.apply(get_args.transfer.options()?)
+            };
+            let (client, remotes) = resolve_all(&mut resolver, &get_args.remote, glob).await?;
+            let mut failures = SourceFailures::new(continue_on_error);
+            for remote in &remotes {
+                let result = client
+                    .download_files(remote, &get_args.local, &options)
+                    .await;
+                failures.record(Action::Download, remote, result)?;
+            }
+            failures.finish()?;
         }
         Commands::Du(du_args) => {
-            client.disk_usage(&du_args.path, du_args.summary).await?;
+            let filter = du_args.filter.filter()?;
+            let (client, paths) = resolve_all(&mut resolver, &du_args.path, glob).await?;
+            for path in &paths {
+                let usage = client
+                    .disk_usage(path, du_args.summary, &filter, &reporter)
+                    .await?;
+                if du_args.summary {
+                    renderer.usage_total(&usage.total);
+                }
+            }
         }
         Commands::Put(put_args) => {
+            let (client, remote) = resolver.resolve(&put_args.remote).await?;
             client
-                .upload_files(&put_args.local, &put_args.remote, put_args.recursive)
+                .upload_files(
+                    &put_args.local,
+                    &remote,
+                    put_args.recursive,
+                    &TransferOptions {
+                        resume: put_args.resume,
+                        dry_run: dry_run.cloned(),
+                        reporter,
+                        rate_limit: rate_limit.cloned(),
+                        continue_on_error,
+                        ..put_args.parts.apply(put_args.transfer.options()?)
+                    },
+                )
                 .await?;
         }
         Commands::Rm(rm_args) => {
-            if !confirm_deletion(&rm_args.paths, rm_args.force)? {
+            let filter = rm_args.filter.filter()?;
+            let groups = group_by_client(&mut resolver, &rm_args.paths, glob).await?;
+            let expanded: Vec<String> = groups
+                .iter()
+                .flat_map(|(_, paths)| paths.iter().cloned())
+                .collect();
+            if dry_run.is_none() && !confirm_deletion(&expanded, rm_args.force)? {
                 println!("Operation cancelled.");
                 return Ok(());
             }
-            client
-                .delete_files(&rm_args.paths, rm_args.recursive)
-                .await?;
+            for (client, paths) in groups {
+                client
+                    .delete_files(&paths, rm_args.recursive, &filter, dry_run, &reporter)
+                    .await?;
+            }
         }
         Commands::Cp(cp_args) => {
-            client
-                .copy_files(&cp_args.src_path, &cp_args.dest_path)
-                .await?;
+            let options = TransferOptions {
+                server_side: !cp_args.no_server_side,
+                dry_run: dry_run.cloned(),
+                reporter,
+                rate_limit: rate_limit.cloned(),
+                continue_on_error,
+                ..cp_args.transfer.options()?
+            };
+            let (src_client, srcs) = resolve_all(&mut resolver, &cp_args.src_path, glob).await?;
+            let (dest_client, dest) = resolver.resolve(&cp_args.dest_path).await?;
+            ensure_directory_target(&dest_client, &dest, srcs.len()).await?;
+            let mut failures = SourceFailures::new(continue_on_error);
+            for src in &srcs {
+                let result = src_client
+                    .copy_files_to(&dest_client, src, &dest, &options)
+                    .await;
+                failures.record(Action::Copy, src, result)?;
+            }
+            failures.finish()?;
         }
         Commands::Mv(mv_args) => {
-            client
-                .move_files(&mv_args.src_path, &mv_args.dest_path)
+            let options = TransferOptions {
+                server_side: !mv_args.no_server_side,
+                dry_run: dry_run.cloned(),
+                reporter,
+                rate_limit: rate_limit.cloned(),
+                continue_on_error,
+                ..mv_args.transfer.options()?
+            };
+            let (src_client, srcs) = resolve_all(&mut resolver, &mv_args.src_path, glob).await?;
+            let (dest_client, dest) = resolver.resolve(&mv_args.dest_path).await?;
+            ensure_directory_target(&dest_client, &dest, srcs.len()).await?;
+            let mut failures = SourceFailures::new(continue_on_error);
+            for src in &srcs {
+                let result = src_client
+                    .move_files_to(&dest_client, src, &dest, &options)
+                    .await;
+                failures.record(Action::Move, src, result)?;
+            }
+            failures.finish()?;
+        }
+        Commands::Sync(sync_args) => {
+            let options = TransferOptions {
+                filter: sync_args.filter.filter()?,
+                dry_run: dry_run.cloned(),
+                reporter,
+                rate_limit: rate_limit.cloned(),
+                continue_on_error,
+                ..TransferOptions::default()
+            };
+            let (src_client, src) = resolve_one(&mut resolver, &sync_args.src_path, glob).await?;
+            let (dest_client, dest) = resolver.resolve(&sync_args.dest_path).await?;
+            let report = src_client
+                .sync_to(&dest_client, &src, &dest, sync_args.delete, &options)
                 .await?;
+            if dry_run.is_none() {
+                renderer.sync_summary(&report);
+            }
+        }
+        Commands::Diff(diff_args) => {
+            let format = if diff_args.json {
+                OutputFormat::Json
+            } else {
+                renderer.format()
+            };
+            let (old_client, old) = resolve_one(&mut resolver, &diff_args.old_path, glob).await?;
+            let (new_client, new) = resolve_one(&mut resolver, &diff_args.new_path, glob).await?;
+            let diff = old_client
+                .diff_with(&new_client, &old, &new, &diff_args.filter.filter()?)
+                .await?;
+            render::print_diff(format, diff_args.stat, &diff)?;
         }
         Commands::Mkdir(mkdir_args) => {
+            let (client, path) = resolver.resolve(&mkdir_args.path).await?;
             client
-                .create_directory(&mkdir_args.path, mkdir_args.parents)
+                .create_directory(&path, mkdir_args.parents, dry_run, &reporter)
                 .await?;
         }
         Commands::Cat(cat_args) => {
-            client
-                .cat_file(&cat_args.path, cat_args.force, cat_args.size_limit_mb)
+            let (client, paths) = resolve_all(&mut resolver, &cat_args.path, glob).await?;
+            let limit_mb = cat_args.size_limit_mb;
+            for path in &paths {
+                let size_mb = client.cat_size(path).await?.div_ceil(1024 * 1024);
+                if limit_mb > 0
+                    && size_mb > limit_mb
+                    && !cat_args.force
+                    && !confirm_large_file(size_mb, limit_mb)?
+                {
+                    continue;
+                }
+                client.cat_file(path, &mut io::stdout(), rate_limit).await?;
+            }
+        }
+        Commands::Checksum(checksum_args) => {
+            let filter = checksum_args.filter.filter()?;
+            for input in &checksum_args.paths {
+                let (client, paths) = resolve_all(&mut resolver, input, glob).await?;
+                for path in &paths {
+                    client
+                        .checksum_files(
+                            path,
+                            checksum_args.algo,
+                            checksum_args.manifest,
+                            &filter,
+                            &reporter,
+                        )
+                        .await?;
+                }
+            }
+        }
+        Commands::Verify(verify_args) => {
+            let format = if verify_args.json {
+                OutputFormat::Json
+            } else {
+                renderer.format()
+            };
+            let (client, remote) = resolve_one(&mut resolver, &verify_args.remote, glob).await?;
+            let report = client
+                .verify_tree(
+                    &verify_args.local,
+                    &remote,
+                    verify_args.checksum,
+                    &verify_args.filter.filter()?,
+                )
                 .await?;
+            render::print_verify(format, &report)?;
+            if !report.is_clean() {
+                return Err(Error::TreeMismatch {
+                    missing: report.missing.len(),
+                    extra: report.extra.len(),
+                    differing: report.differing.len(),
+                });
+            }
+        }
+        Commands::Find(find_args) => {
+            let (client, paths) = resolve_all(&mut resolver, &find_args.path, glob).await?;
+            let predicates = find_args.predicates();
+            for path in &paths {
+                let found = client.find_paths(path, &predicates).await?;
+                if !find_args.delete {
+                    for entry in &found {
+                        if find_args.print0 {
+                            print!("{entry}\0");
+                        } else {
+                            println!("{entry}");
+                        }
+                    }
+                } else if found.is_empty() {
+                    println!("No matching entries.");
+                } else if dry_run.is_some() || confirm_deletion(&found, find_args.force)? {
+                    client.delete_found(&found, dry_run, &reporter).await?;
+                } else {
+                    println!("Operation cancelled.");
+                }
+            }
         }
         Commands::Stat(stat_args) => {
             let format = if stat_args.json {
@@ -236,10 +1020,138 @@ pub async fn run(args: Args, client: StorageClient) -> Result<()> {
             } else if stat_args.raw {
                 OutputFormat::Raw
             } else {
-                OutputFormat::Human
+                renderer.format()
             };
-            client.stat_metadata(&stat_args.path, format).await?;
+            let (client, paths) = resolve_all(&mut resolver, &stat_args.path, glob).await?;
+            for path in &paths {
+                let meta = client.stat_metadata(path).await?;
+                render::print_stat(format, &meta)?;
+            }
         }
     }
     Ok(())
 }
+
+/// Failures of a transfer from several sources, e.g. the paths a glob pattern matched.
+///
+/// With `--continue-on-error` every source is transferred, and the failures of all of
+/// them end up in a single `Error::PartialTransfer`, as if they formed one job. Otherwise
+/// the first failing source stops the command.
+struct SourceFailures {
+    continue_on_error: bool,
+    failures: Vec<Failure>,
+}
+
+impl SourceFailures {
+    fn new(continue_on_error: bool) -> Self {
+        Self {
+            continue_on_error,
+            failures: Vec::new(),
+        }
+    }
+
+    /// Collect the failures of transferring `src`, or return them when not continuing.
+    fn record<T>(&mut self, action: Action, src: &str, result: Result<T>) -> Result<()> {
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if !self.continue_on_error => Err(e),
+            Err(e) => {
+                match e.failures() {
+                    Some(failures) => self.failures.extend_from_slice(failures),
+                    None => self.failures.push(Failure::from_error(action, src, &e)),
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Fail with every collected failure, if any.
+    fn finish(self) -> Result<()> {
+        if self.failures.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::PartialTransfer {
+                failures: self.failures,
+            })
+        }
+    }
+}
+
+/// Resolve a remote path argument, expanding a glob pattern into the matching paths
+/// unless `glob` is false.
+async fn resolve_all(
+    resolver: &mut ClientResolver,
+    input: &str,
+    glob: bool,
+) -> Result<(StorageClient, Vec<String>)> {
+    let (client, path) = resolver.resolve(input).await?;
+    let paths = if glob {
+        client.expand_glob(&path).await?
+    } else {
+        vec![path]
+    };
+    Ok((client, paths))
+}
+
+/// Resolve a remote path argument whose glob pattern must match exactly one path.
+async fn resolve_one(
+    resolver: &mut ClientResolver,
+    input: &str,
+    glob: bool,
+) -> Result<(StorageClient, String)> {
+    let (client, mut paths) = resolve_all(resolver, input, glob).await?;
+    if paths.len() != 1 {
+        return Err(Error::AmbiguousGlob {
+            pattern: input.to_string(),
+            count: paths.len(),
+        });
+    }
+    Ok((client, paths.remove(0)))
+}
+
+/// Several sources can only go into a directory, like `cp a b c` in POSIX: the target
+/// must exist as one or be spelled with a trailing slash.
+async fn ensure_directory_target(client: &StorageClient, dest: &str, count: usize) -> Result<()> {
+    if count > 1 && !dest.ends_with('/') && !client.is_directory(dest).await {
+        return Err(Error::TargetNotDirectory {
+            path: dest.to_string(),
+            count,
+        });
+    }
+    Ok(())
+}
+
+/// Resolve several path arguments, grouping the keys by the client that serves them.
+///
+/// Glob patterns are expanded unless `glob` is false.
+async fn group_by_client(
+    resolver: &mut ClientResolver,
+    paths: &[String],
+    glob: bool,
+) -> Result<Vec<(StorageClient, Vec<String>)>> {
+    let mut groups: Vec<(StorageTarget, StorageClient, Vec<String>)> = Vec::new();
+    for input in paths {
+        let parsed = resolver.parse(input)?;
+        let index = match groups
+            .iter()
+            .position(|(target, _, _)| *target == parsed.target)
+        {
+            Some(index) => index,
+            None => {
+                let client = resolver.client(&parsed.target).await?;
+                groups.push((parsed.target, client, Vec::new()));
+                groups.len() - 1
+            }
+        };
+        let (_, client, keys) = &mut groups[index];
+        if glob {
+            keys.extend(client.expand_glob(&parsed.path).await?);
+        } else {
+            keys.push(parsed.path);
+        }
+    }
+    Ok(groups
+        .into_iter()
+        .map(|(_, client, keys)| (client, keys))
+        .collect())
+}
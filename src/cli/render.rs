@@ -0,0 +1,391 @@
+// Rendering of operation results in the selected output format
+use crate::error::Result;
+use crate::storage::{
+    Action, ChecksumEntry, ConsoleProgress, DryRun, Failure, Item, ItemObserver, ItemStatus,
+    ListEntry, ObjectMeta, OutputFormat, PathRecord, ProgressEvent, ProgressObserver, SyncReport,
+    TransferMethod, TransferRecord, TreeDiff, UsageEntry, VerifyReport, format_size,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Mutex;
+
+/// Prints the per-item results of a command as operations deliver them.
+///
+/// Human output goes to stdout, except for problems (failures, missing or skipped
+/// paths), which go to stderr. `raw` prints one line of `key=value` pairs per item,
+/// `jsonl` one JSON object per item, and `json` collects the items into a single array
+/// printed by `finish`.
+#[derive(Debug)]
+pub struct Renderer {
+    format: OutputFormat,
+    long: bool,
+    manifest: bool,
+    items: Mutex<Vec<Value>>,
+}
+
+impl Renderer {
+    pub fn new(format: OutputFormat) -> Self {
+        Self {
+            format,
+            long: false,
+            manifest: false,
+            items: Mutex::default(),
+        }
+    }
+
+    /// Show listed entries with type, size and modification time (`ls -L`).
+    pub fn long_listing(self, long: bool) -> Self {
+        Self { long, ..self }
+    }
+
+    /// Show checksums as `sha256sum`-compatible lines, without ETags (`checksum --manifest`).
+    pub fn manifest(self, manifest: bool) -> Self {
+        Self { manifest, ..self }
+    }
+
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    /// Print the total of `du -s`, which humans see with the number of files.
+    pub fn usage_total(&self, total: &UsageEntry) {
+        if self.format == OutputFormat::Human {
+            println!("{} {}", format_size(total.size), total.path);
+            println!("Total files: {}", total.files);
+        } else {
+            self.on_item(Item::Usage(total));
+        }
+    }
+
+    /// Print the counters of a completed sync, for human output.
+    pub fn sync_summary(&self, report: &SyncReport) {
+        if self.format == OutputFormat::Human {
+            println!(
+                "Sync complete: {} copied, {} skipped, {} deleted",
+                report.copied.len(),
+                report.skipped,
+                report.deleted.len()
+            );
+        }
+    }
+
+    /// Print the totals of every change a dry run recorded, for human output.
+    pub fn dry_run_summary(&self, plan: &DryRun) {
+        if self.format != OutputFormat::Human {
+            return;
+        }
+        let totals = plan.totals();
+        if totals.is_empty() {
+            println!("Dry run: nothing to do");
+            return;
+        }
+        let parts: Vec<String> = totals
+            .iter()
+            .map(|(action, count, bytes)| match action {
+                Action::Mkdir | Action::Delete => format!("{count} to {}", verb(*action)),
+                _ => format!("{count} to {} ({})", verb(*action), format_size(*bytes)),
+            })
+            .collect();
+        println!("Dry run: {}; no changes made", parts.join(", "));
+    }
+
+    /// Print the paths a command failed on, for human output, once everything else is done.
+    pub fn failure_summary(&self, failures: &[Failure]) {
+        if self.format != OutputFormat::Human || failures.is_empty() {
+            return;
+        }
+        eprintln!("{} path(s) failed:", failures.len());
+        for failure in failures {
+            eprintln!("  {} ({})", failure.path, failure.kind);
+        }
+    }
+
+    /// Print the collected items of `json` output.
+    ///
+    /// With `always`, an empty array is printed when there are no items, so that
+    /// commands producing items always print valid JSON.
+    pub fn finish(&self, always: bool) -> Result<()> {
+        let items = self.items.lock().unwrap();
+        if self.format == OutputFormat::Json && (always || !items.is_empty()) {
+            println!("{}", serde_json::to_string(&*items)?);
+        }
+        Ok(())
+    }
+
+    fn human(&self, item: Item<'_>) {
+        match item {
+            Item::Transfer(record) => match record.status {
+                ItemStatus::Ok | ItemStatus::Planned => println!("{}", transfer_line(record)),
+                ItemStatus::Skipped => {}
+                _ => eprintln!("{}", transfer_line(record)),
+            },
+            Item::Path(record) => match record.status {
+                ItemStatus::Ok | ItemStatus::Planned | ItemStatus::Exists => {
+                    println!("{}", path_line(record))
+                }
+                _ => eprintln!("{}", path_line(record)),
+            },
+            Item::Entry(entry) => println!("{}", self.entry_line(entry)),
+            Item::Usage(usage) => println!("{} {}", format_size(usage.size), usage.path),
+            Item::Checksum(checksum) => println!("{}", self.checksum_line(checksum)),
+        }
+    }
+
+    fn entry_line(&self, entry: &ListEntry) -> String {
+        if !self.long {
+            return entry.path.clone();
+        }
+        let file_type = if entry.is_dir() { "DIR" } else { "FILE" };
+        let size_str = if entry.is_dir() {
+            "-".to_string()
+        } else {
+            format_size(entry.size)
+        };
+        let modified = entry.last_modified.as_deref().unwrap_or("Unknown");
+        format!("{file_type:<6} {size_str:>10} {modified} {}", entry.path)
+    }
+
+    fn checksum_line(&self, checksum: &ChecksumEntry) -> String {
+        let (hash, path) = (&checksum.hash, &checksum.path);
+        if self.manifest {
+            return format!("{hash}  {path}");
+        }
+        let etag = checksum.etag.as_deref().unwrap_or("-");
+        format!("{hash}  {etag}  {path}")
+    }
+}
+
+impl ItemObserver for Renderer {
+    fn on_item(&self, item: Item<'_>) {
+        match self.format {
+            OutputFormat::Human => self.human(item),
+            OutputFormat::Raw => println!("{}", raw_line(&to_value(&item))),
+            OutputFormat::Jsonl => println!("{}", to_value(&item)),
+            OutputFormat::Json => self.items.lock().unwrap().push(to_value(&item)),
+        }
+    }
+}
+
+/// Shows the progress of transfers: the progress line of a `ConsoleProgress` for human
+/// output, and the notes operations send about resumed files and warnings.
+///
+/// Warnings go to stderr in every format; notes about resumed files are only printed for
+/// human output, on stdout.
+#[derive(Debug)]
+pub struct ProgressPrinter {
+    format: OutputFormat,
+    console: Option<ConsoleProgress>,
+}
+
+impl ProgressPrinter {
+    pub fn new(format: OutputFormat) -> Self {
+        // Progress lines would only get in the way of machine-readable output.
+        let console = (format == OutputFormat::Human).then(ConsoleProgress::new);
+        Self { format, console }
+    }
+}
+
+impl ProgressObserver for ProgressPrinter {
+    fn on_event(&self, event: ProgressEvent<'_>) {
+        if let Some(console) = &self.console {
+            console.on_event(event);
+        }
+        match event {
+            ProgressEvent::FileResumed { path, offset } if self.format == OutputFormat::Human => {
+                println!("Resuming {path} from byte {offset}");
+            }
+            ProgressEvent::Warning { message } => eprintln!("Warning: {message}"),
+            _ => {}
+        }
+    }
+}
+
+/// Verb describing an action in human messages.
+fn verb(action: Action) -> &'static str {
+    match action {
+        Action::Upload => "upload",
+        Action::Download => "download",
+        Action::Copy => "copy",
+        Action::Move => "move",
+        Action::Sync => "sync",
+        Action::Mkdir => "create directory",
+        Action::Delete => "delete",
+    }
+}
+
+fn transfer_line(record: &TransferRecord) -> String {
+    let (src, dest) = (&record.src, &record.dest);
+    let bytes = record.bytes.unwrap_or_default();
+    match record.status {
+        ItemStatus::Ok => {}
+        ItemStatus::Failed => {
+            let error = record.error.as_deref().unwrap_or_default();
+            return format!("Failed to transfer {src}: {error}");
+        }
+        _ => {
+            return format!(
+                "Would {}: {src} → {dest} ({bytes} bytes)",
+                verb(record.action)
+            );
+        }
+    }
+    let detail = match (record.method, record.action) {
+        (Some(TransferMethod::ServerSideCopy), Action::Copy) => "server-side".to_string(),
+        (Some(TransferMethod::ServerSideCopy), _) => "server-side copy".to_string(),
+        (Some(TransferMethod::ServerSideRename), _) => "server-side rename".to_string(),
+        _ => format!("{bytes} bytes"),
+    };
+    match record.action {
+        Action::Upload => format!("✅ Upload: {src} → {dest} ({detail})"),
+        Action::Download => format!("Downloaded: {src} → {dest} ({detail})"),
+        Action::Copy => format!("✅ Copied: {src} → {dest} ({detail})"),
+        Action::Move => format!("✅ Moved: {src} → {dest} ({detail})"),
+        _ => format!("✅ Synced: {src} → {dest} ({detail})"),
+    }
+}
+
+fn path_line(record: &PathRecord) -> String {
+    let path = &record.path;
+    match (record.status, record.action) {
+        (ItemStatus::Ok, Action::Mkdir) => format!("Created directory: {path}"),
+        (ItemStatus::Ok, _) => format!("Deleted: {path}"),
+        (ItemStatus::Planned, action) => format!("Would {}: {path}", verb(action)),
+        (ItemStatus::Exists, _) if path.is_empty() || path == "/" => {
+            "Note: Root directory '/' already exists (bucket root)".to_string()
+        }
+        (ItemStatus::Exists, _) => format!("Directory already exists: {path}"),
+        (ItemStatus::Skipped, _) => format!("Skipping non-empty directory: {path}"),
+        (ItemStatus::Missing, _) => format!("Path not found: {path}"),
+        (ItemStatus::Failed, action) => {
+            let error = record.error.as_deref().unwrap_or_default();
+            format!("Failed to {} {path}: {error}", verb(action))
+        }
+    }
+}
+
+fn to_value<T: Serialize>(item: &T) -> Value {
+    // Items are plain structs with string keys, which always serialize.
+    serde_json::to_value(item).expect("output items serialize to JSON")
+}
+
+/// Render an item as `key=value` pairs, leaving out absent fields.
+fn raw_line(value: &Value) -> String {
+    let Value::Object(fields) = value else {
+        return value.to_string();
+    };
+    fields
+        .iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(key, value)| match value {
+            Value::String(s) => format!("{key}={s}"),
+            other => format!("{key}={other}"),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Print a tree comparison; with `stat`, only its counters.
+pub fn print_diff(format: OutputFormat, stat: bool, diff: &TreeDiff) -> Result<()> {
+    let summary = diff.summary();
+    match format {
+        OutputFormat::Json | OutputFormat::Jsonl if stat => {
+            println!("{}", serde_json::to_string(&summary)?)
+        }
+        OutputFormat::Json | OutputFormat::Jsonl => println!("{}", serde_json::to_string(diff)?),
+        OutputFormat::Human | OutputFormat::Raw => {
+            if !stat {
+                for entry in &diff.added {
+                    println!("+ {} ({})", entry.path, format_size(entry.size));
+                }
+                for entry in &diff.removed {
+                    println!("- {} ({})", entry.path, format_size(entry.size));
+                }
+                for entry in &diff.modified {
+                    println!("M {} ({})", entry.path, entry.reason);
+                }
+            }
+            println!(
+                "Diff: {} added ({}), {} removed ({}), {} modified, {} unchanged",
+                summary.added,
+                format_size(summary.added_bytes),
+                summary.removed,
+                format_size(summary.removed_bytes),
+                summary.modified,
+                summary.unchanged
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Print the outcome of comparing a local tree with a remote prefix.
+pub fn print_verify(format: OutputFormat, report: &VerifyReport) -> Result<()> {
+    match format {
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            println!("{}", serde_json::to_string(report)?)
+        }
+        OutputFormat::Human | OutputFormat::Raw => {
+            for path in &report.missing {
+                println!("Missing: {path}");
+            }
+            for path in &report.extra {
+                println!("Extra: {path}");
+            }
+            for diff in &report.differing {
+                println!(
+                    "Differs: {} ({} local={} remote={})",
+                    diff.path, diff.field, diff.local, diff.remote
+                );
+            }
+            println!(
+                "Verify: {} matched, {} missing, {} extra, {} differing",
+                report.matched,
+                report.missing.len(),
+                report.extra.len(),
+                report.differing.len()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Print object metadata as `key=value` lines or a JSON object.
+pub fn print_stat(format: OutputFormat, meta: &ObjectMeta) -> Result<()> {
+    match format {
+        OutputFormat::Human | OutputFormat::Raw => {
+            println!("path={}", meta.path);
+            println!("type={}", meta.entry_type);
+            println!("size={}", meta.size);
+            if let Some(t) = meta.last_modified {
+                println!("last_modified={}", t);
+            }
+            if let Some(etag) = &meta.etag {
+                println!("etag=\"{}\"", etag);
+            }
+            if let Some(ct) = &meta.content_type {
+                println!("content_type={}", ct);
+            }
+        }
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            #[derive(Serialize)]
+            struct JsonMeta<'a> {
+                path: &'a str,
+                entry_type: &'a str,
+                size: u64,
+                last_modified: Option<String>,
+                etag: Option<&'a str>,
+                content_type: Option<&'a str>,
+            }
+            let json = JsonMeta {
+                path: &meta.path,
+                entry_type: &meta.entry_type,
+                size: meta.size,
+                last_modified: meta.last_modified.map(|t| t.to_string()),
+                etag: meta.etag.as_deref(),
+                content_type: meta.content_type.as_deref(),
+            };
+            println!("{}", serde_json::to_string(&json)?);
+        }
+    }
+    Ok(())
+}
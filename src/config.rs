@@ -1,9 +1,13 @@
 use crate::error::{Error, Result};
-use crate::storage::constants::DEFAULT_FS_ROOT;
-use crate::storage::{StorageConfig, StorageProvider};
+use crate::storage::constants::{CONFIG_DIR_NAME, CONFIG_FILE_NAME, DEFAULT_FS_ROOT};
+use crate::storage::{StorageConfig, StorageProvider, parse_size};
 use log::warn;
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::env;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 
 /// Read the first available environment variable from a list of keys
 fn env_any(keys: &[&str]) -> Option<String> {
@@ -15,13 +19,6 @@ fn env_any(keys: &[&str]) -> Option<String> {
     None
 }
 
-/// Read a required environment variable from a list of keys
-fn env_any_required(keys: &[&str]) -> Result<String> {
-    env_any(keys).ok_or_else(|| Error::MissingEnvVar {
-        key: keys.join(" or "),
-    })
-}
-
 /// Provider-specific environment variable keys
 struct ProviderKeys {
     bucket: Vec<&'static str>,
@@ -82,34 +79,390 @@ fn s3_like_keys(provider_str: &str) -> ProviderKeys {
     }
 }
 
-/// Load storage configuration from environment variables
+/// A named storage profile as written in the config file.
+///
+/// Every field is optional so that a profile can hold only what differs from the
+/// environment, e.g. just a `bucket` while credentials come from `STORAGE_*` variables.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub provider: Option<String>,
+    pub bucket: Option<String>,
+    pub access_key_id: Option<String>,
+    pub access_key_secret: Option<String>,
+    pub endpoint: Option<String>,
+    pub region: Option<String>,
+    pub root_path: Option<String>,
+    pub name_node: Option<String>,
+    /// Retries of requests failing with a temporary error
+    pub retries: Option<usize>,
+    /// Timeout of non-IO requests, in seconds
+    pub timeout: Option<u64>,
+    /// Timeout of single reads and writes, in seconds
+    pub io_timeout: Option<u64>,
+    /// Bandwidth limit of transfers, as a size per second such as `20M`
+    pub limit_rate: Option<String>,
+}
+
+/// Retry and timeout settings given on the command line, which win over profile values.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RequestOverrides {
+    pub retries: Option<usize>,
+    /// Timeout of non-IO requests, in seconds
+    pub timeout: Option<u64>,
+    /// Timeout of single reads and writes, in seconds
+    pub io_timeout: Option<u64>,
+}
+
+/// Contents of the storify config file (`~/.config/storify/config.toml` by default).
+///
+/// ```toml
+/// default_profile = "prod"
+///
+/// [profiles.prod]
+/// provider = "oss"
+/// bucket = "my-bucket"
+/// access_key_id = "..."
+/// access_key_secret = "..."
+///
+/// [profiles.local]
+/// provider = "fs"
+/// root_path = "/tmp/storify"
+///
+/// [profiles.flaky]
+/// provider = "cos"
+/// bucket = "archive"
+/// retries = 10
+/// timeout = 120
+/// io_timeout = 60
+/// limit_rate = "20M"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: HashMap<String, Profile>,
+}
+
+/// Location of the config file when none is given explicitly:
+/// `$XDG_CONFIG_HOME/storify/config.toml`, falling back to `$HOME/.config/storify/config.toml`.
+pub fn default_config_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME))
+}
+
+/// Load the config file.
+///
+/// An explicitly provided path must exist; the default location is optional and an
+/// absent file is treated as an empty config.
+pub fn load_config_file(path: Option<&Path>) -> Result<ConfigFile> {
+    let (path, required) = match path {
+        Some(p) => (p.to_path_buf(), true),
+        None => match default_config_path() {
+            Some(p) => (p, false),
+            None => return Ok(ConfigFile::default()),
+        },
+    };
+
+    if !path.exists() {
+        if required {
+            return Err(Error::PathNotFound { path });
+        }
+        return Ok(ConfigFile::default());
+    }
+
+    let content = std::fs::read_to_string(&path)?;
+    toml::from_str(&content).map_err(|e| Error::InvalidConfig {
+        path,
+        message: e.to_string(),
+    })
+}
+
+/// Resolves storage configuration from CLI flags, environment variables and config file profiles.
+///
+/// A profile selected explicitly (`--profile` or `STORIFY_PROFILE`) wins over environment
+/// variables, which in turn win over the config file's `default_profile`; built-in
+/// defaults come last. Paths naming a profile or bucket explicitly (see [`crate::storage::location`]) are
+/// resolved through [`ConfigLoader::load_profile`] and [`ConfigLoader::load_bucket`].
+/// Retry and timeout settings come from the command line, then from the profile.
+pub struct ConfigLoader {
+    file: ConfigFile,
+    profile: Option<String>,
+    /// Whether `profile` was selected explicitly rather than by `default_profile`
+    explicit_profile: bool,
+    overrides: RequestOverrides,
+}
+
+impl ConfigLoader {
+    /// Read the config file and select the active profile.
+    ///
+    /// # Arguments
+    /// * `config_path` - Explicit config file path; the default location is used when `None`
+    /// * `profile` - Profile selected on the command line; falls back to `default_profile`
+    pub fn new(config_path: Option<&Path>, profile: Option<&str>) -> Result<Self> {
+        let file = load_config_file(config_path)?;
+        let explicit_profile = profile.is_some();
+        let profile = profile
+            .map(str::to_string)
+            .or_else(|| file.default_profile.clone());
+
+        if let Some(name) = &profile
+            && !file.profiles.contains_key(name)
+        {
+            return Err(Error::ProfileNotFound { name: name.clone() });
+        }
+
+        Ok(Self {
+            file,
+            profile,
+            explicit_profile,
+            overrides: RequestOverrides::default(),
+        })
+    }
+
+    /// Use retry and timeout settings given on the command line.
+    pub fn with_overrides(self, overrides: RequestOverrides) -> Self {
+        Self { overrides, ..self }
+    }
+
+    /// Name of the active profile, if any.
+    pub fn active_profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Whether the config file defines a profile with the given name.
+    pub fn has_profile(&self, name: &str) -> bool {
+        self.file.profiles.contains_key(name)
+    }
+
+    /// Bandwidth limit of the active profile, in bytes per second.
+    pub fn limit_rate(&self) -> Result<Option<u64>> {
+        let Some(value) = self.active_profile_values().limit_rate else {
+            return Ok(None);
+        };
+        match parse_size(&value) {
+            Some(rate) if rate >= 1 => Ok(Some(rate)),
+            _ => Err(Error::InvalidArgument {
+                name: "limit_rate".to_string(),
+                value,
+            }),
+        }
+    }
+
+    /// Build the storage configuration used for bare paths.
+    ///
+    /// The values of an explicitly selected profile win over environment variables;
+    /// those of the `default_profile` only fill in what the environment leaves unset.
+    pub fn load(&self) -> Result<StorageConfig> {
+        let profile = self.active_profile_values();
+        let source = ValueSource {
+            profile: &profile,
+            profile_name: self.profile.as_deref(),
+            profile_first: self.explicit_profile,
+        };
+        let config = resolve_config(&source, None, None)?;
+        Ok(self.apply_requests(config, &profile))
+    }
+
+    /// Build the storage configuration for a profile addressed explicitly by a
+    /// `profile-name:/key` path. The named profile's values win over environment variables.
+    pub fn load_profile(&self, name: &str) -> Result<StorageConfig> {
+        let profile = self
+            .file
+            .profiles
+            .get(name)
+            .ok_or_else(|| Error::ProfileNotFound {
+                name: name.to_string(),
+            })?;
+        let source = ValueSource {
+            profile,
+            profile_name: Some(name),
+            profile_first: true,
+        };
+        let config = resolve_config(&source, None, None)?;
+        Ok(self.apply_requests(config, profile))
+    }
+
+    /// Build the storage configuration for a `scheme://bucket/key` path.
+    ///
+    /// Credentials come from the environment, then from a profile declaring the same
+    /// provider and bucket, then from the active profile if it uses the same provider.
+    /// An explicitly selected active profile wins over the environment, as in `load`.
+    pub fn load_bucket(&self, provider: &str, bucket: &str) -> Result<StorageConfig> {
+        let wanted = StorageProvider::from_str(provider)?;
+        let same_provider = |p: &Profile| {
+            p.provider
+                .as_deref()
+                .and_then(|s| StorageProvider::from_str(s).ok())
+                == Some(wanted)
+        };
+
+        let named = self
+            .file
+            .profiles
+            .iter()
+            .find(|(_, p)| same_provider(p) && p.bucket.as_deref() == Some(bucket))
+            .map(|(name, p)| (Some(name.as_str()), p.clone()));
+        let (profile_name, profile) = named.unwrap_or_else(|| {
+            let active = self.active_profile_values();
+            if same_provider(&active) {
+                (self.profile.as_deref(), active)
+            } else {
+                (None, Profile::default())
+            }
+        });
+
+        let source = ValueSource {
+            profile: &profile,
+            profile_name,
+            profile_first: self.explicit_profile && profile_name == self.profile.as_deref(),
+        };
+        let config = resolve_config(&source, Some(provider), Some(bucket))?;
+        Ok(self.apply_requests(config, &profile))
+    }
+
+    /// Build the storage configuration for `fs:///` paths on the local filesystem.
+    pub fn load_local(&self) -> StorageConfig {
+        self.apply_requests(StorageConfig::fs("/".to_string()), &Profile::default())
+    }
+
+    /// Set the retry and timeout settings of `profile`, overridden by the command line.
+    fn apply_requests(&self, mut config: StorageConfig, profile: &Profile) -> StorageConfig {
+        let requests = &mut config.requests;
+        if let Some(retries) = self.overrides.retries.or(profile.retries) {
+            requests.retries = retries;
+        }
+        if let Some(secs) = self.overrides.timeout.or(profile.timeout) {
+            requests.timeout = Duration::from_secs(secs);
+        }
+        if let Some(secs) = self.overrides.io_timeout.or(profile.io_timeout) {
+            requests.io_timeout = Duration::from_secs(secs);
+        }
+        config
+    }
+
+    fn active_profile_values(&self) -> Profile {
+        self.profile
+            .as_ref()
+            .and_then(|name| self.file.profiles.get(name))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Load storage configuration from environment variables and the default config file
 pub fn load_storage_config() -> Result<StorageConfig> {
-    let provider_str = env::var("STORAGE_PROVIDER").unwrap_or_else(|_| {
-        warn!("STORAGE_PROVIDER not set, using default: oss");
-        "oss".to_string()
-    });
+    ConfigLoader::new(None, None)?.load()
+}
+
+/// Resolve a full `StorageConfig` from environment variables and profile values.
+///
+/// `provider` and `bucket` override whatever the environment or profile say, and are
+/// used for paths that name them explicitly.
+fn resolve_config(
+    source: &ValueSource<'_>,
+    provider: Option<&str>,
+    bucket: Option<&str>,
+) -> Result<StorageConfig> {
+    let provider_str = match provider {
+        Some(p) => p.to_string(),
+        None => source
+            .optional(&["STORAGE_PROVIDER"], |p| &p.provider)
+            .unwrap_or_else(|| {
+                warn!("STORAGE_PROVIDER not set, using default: oss");
+                "oss".to_string()
+            }),
+    };
     let provider = StorageProvider::from_str(&provider_str)?;
 
     match provider {
-        StorageProvider::Oss => load_cloud_config(ProviderKeys::for_oss(), StorageConfig::oss),
-        StorageProvider::S3 => load_cloud_config(s3_like_keys(&provider_str), StorageConfig::s3),
-        StorageProvider::Cos => load_cloud_config(ProviderKeys::for_cos(), StorageConfig::cos),
-        StorageProvider::Fs => load_fs_config(),
-        StorageProvider::Hdfs => load_hdfs_config(),
+        StorageProvider::Oss => {
+            load_cloud_config(ProviderKeys::for_oss(), source, bucket, StorageConfig::oss)
+        }
+        StorageProvider::S3 => load_cloud_config(
+            s3_like_keys(&provider_str),
+            source,
+            bucket,
+            StorageConfig::s3,
+        ),
+        StorageProvider::Cos => {
+            load_cloud_config(ProviderKeys::for_cos(), source, bucket, StorageConfig::cos)
+        }
+        StorageProvider::Fs => Ok(load_fs_config(source)),
+        StorageProvider::Hdfs => load_hdfs_config(source),
+    }
+}
+
+/// Looks up a single setting in the environment and in a profile.
+///
+/// Environment variables win unless `profile_first` is set, which is the case for
+/// profiles selected on the command line or addressed explicitly by a path argument.
+struct ValueSource<'a> {
+    profile: &'a Profile,
+    profile_name: Option<&'a str>,
+    profile_first: bool,
+}
+
+impl ValueSource<'_> {
+    fn optional(
+        &self,
+        keys: &[&str],
+        field: impl Fn(&Profile) -> &Option<String>,
+    ) -> Option<String> {
+        let from_profile = || field(self.profile).clone();
+        if self.profile_first {
+            from_profile().or_else(|| env_any(keys))
+        } else {
+            env_any(keys).or_else(from_profile)
+        }
+    }
+
+    fn required(
+        &self,
+        keys: &[&str],
+        field_name: &str,
+        field: impl Fn(&Profile) -> &Option<String>,
+    ) -> Result<String> {
+        self.optional(keys, field)
+            .ok_or_else(|| match self.profile_name {
+                Some(name) => Error::MissingProfileValue {
+                    profile: name.to_string(),
+                    field: field_name.to_string(),
+                    keys: keys.join(" or "),
+                },
+                None => Error::MissingEnvVar {
+                    key: keys.join(" or "),
+                },
+            })
     }
 }
 
 /// Load configuration for any cloud storage provider
-fn load_cloud_config<F>(keys: ProviderKeys, config_constructor: F) -> Result<StorageConfig>
+fn load_cloud_config<F>(
+    keys: ProviderKeys,
+    source: &ValueSource<'_>,
+    bucket: Option<&str>,
+    config_constructor: F,
+) -> Result<StorageConfig>
 where
     F: FnOnce(String, String, String, Option<String>) -> StorageConfig,
 {
-    let bucket = env_any_required(&keys.bucket)?;
-    let access_key_id = env_any_required(&keys.access_key_id)?;
-    let secret_key = env_any_required(&keys.secret_key)?;
+    let bucket = match bucket {
+        Some(b) => b.to_string(),
+        None => source.required(&keys.bucket, "bucket", |p| &p.bucket)?,
+    };
+    let access_key_id =
+        source.required(&keys.access_key_id, "access_key_id", |p| &p.access_key_id)?;
+    let secret_key = source.required(&keys.secret_key, "access_key_secret", |p| {
+        &p.access_key_secret
+    })?;
 
-    let region = env_any(&keys.region);
-    let endpoint = env_any(&keys.endpoint);
+    let region = source.optional(&keys.region, |p| &p.region);
+    let endpoint = source.optional(&keys.endpoint, |p| &p.endpoint);
 
     let mut config = config_constructor(bucket, access_key_id, secret_key, region);
     config.endpoint = endpoint;
@@ -117,16 +470,18 @@ where
 }
 
 /// Load HDFS configuration
-fn load_hdfs_config() -> Result<StorageConfig> {
-    let name_node = env::var("HDFS_NAME_NODE").map_err(|_| Error::MissingEnvVar {
-        key: "HDFS_NAME_NODE".to_string(),
-    })?;
-    let root_path = env::var("HDFS_ROOT_PATH").unwrap_or_else(|_| "/".to_string());
+fn load_hdfs_config(source: &ValueSource<'_>) -> Result<StorageConfig> {
+    let name_node = source.required(&["HDFS_NAME_NODE"], "name_node", |p| &p.name_node)?;
+    let root_path = source
+        .optional(&["HDFS_ROOT_PATH"], |p| &p.root_path)
+        .unwrap_or_else(|| "/".to_string());
     Ok(StorageConfig::hdfs(name_node, root_path))
 }
 
 /// Load filesystem configuration (for testing)
-fn load_fs_config() -> Result<StorageConfig> {
-    let root_path = env::var("STORAGE_ROOT_PATH").unwrap_or_else(|_| DEFAULT_FS_ROOT.to_string());
-    Ok(StorageConfig::fs(root_path))
+fn load_fs_config(source: &ValueSource<'_>) -> StorageConfig {
+    let root_path = source
+        .optional(&["STORAGE_ROOT_PATH"], |p| &p.root_path)
+        .unwrap_or_else(|| DEFAULT_FS_ROOT.to_string());
+    StorageConfig::fs(root_path)
 }
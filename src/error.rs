@@ -1,3 +1,4 @@
+use crate::storage::{ChecksumAlgo, Failure};
 use snafu::Snafu;
 use std::path::PathBuf;
 
@@ -9,6 +10,21 @@ pub enum Error {
     #[snafu(display("Environment variable '{key}' is required but not found"))]
     MissingEnvVar { key: String },
 
+    #[snafu(display(
+        "Profile '{profile}' does not define '{field}' and no environment variable ({keys}) is set"
+    ))]
+    MissingProfileValue {
+        profile: String,
+        field: String,
+        keys: String,
+    },
+
+    #[snafu(display("Profile '{name}' not found in config file"))]
+    ProfileNotFound { name: String },
+
+    #[snafu(display("Invalid config file '{}': {message}", path.display()))]
+    InvalidConfig { path: PathBuf, message: String },
+
     #[snafu(display(
         "Unsupported storage provider: {provider}. Allowed: 'oss' | 's3' | 'minio' | 'fs'"
     ))]
@@ -20,14 +36,53 @@ pub enum Error {
     #[snafu(display("Invalid path: {path}"))]
     InvalidPath { path: String },
 
+    #[snafu(display("No paths match '{pattern}'"))]
+    NoGlobMatch { pattern: String },
+
+    #[snafu(display("'{pattern}' matches {count} paths, but a single path is expected"))]
+    AmbiguousGlob { pattern: String, count: usize },
+
+    #[snafu(display("Target '{path}' is not a directory, but {count} source paths were given"))]
+    TargetNotDirectory { path: String, count: usize },
+
     #[snafu(display("Cannot delete directory without -R flag: {path}"))]
     DirectoryDeletionNotRecursive { path: String },
 
     #[snafu(display("Use -R to upload directories"))]
     DirectoryUploadNotRecursive,
 
-    #[snafu(display("Partial deletion failure: {} path(s) failed to delete", failed_paths.len()))]
-    PartialDeletion { failed_paths: Vec<String> },
+    #[snafu(display("Partial deletion failure: {} path(s) failed to delete", failures.len()))]
+    PartialDeletion { failures: Vec<Failure> },
+
+    #[snafu(display("Partial transfer failure: {} path(s) failed to transfer", failures.len()))]
+    PartialTransfer { failures: Vec<Failure> },
+
+    #[snafu(display("Invalid value '{value}' for {name}"))]
+    InvalidArgument { name: String, value: String },
+
+    #[snafu(display("Incomplete transfer of '{path}': expected {expected} bytes, got {actual}"))]
+    IncompleteTransfer {
+        path: String,
+        expected: u64,
+        actual: u64,
+    },
+
+    #[snafu(display(
+        "Trees differ: {missing} missing, {extra} extra, {differing} differing file(s)"
+    ))]
+    TreeMismatch {
+        missing: usize,
+        extra: usize,
+        differing: usize,
+    },
+
+    #[snafu(display("Checksum mismatch for '{path}': expected {algo} {expected}, got {actual}"))]
+    ChecksumMismatch {
+        path: String,
+        algo: ChecksumAlgo,
+        expected: String,
+        actual: String,
+    },
 
     #[snafu(display("Failed to delete '{paths}' (recursive: {recursive}): {source}"))]
     DeleteFailed {
@@ -64,6 +119,20 @@ pub enum Error {
         source: Box<Error>,
     },
 
+    #[snafu(display("Failed to diff '{old_path}' against '{new_path}': {source}"))]
+    DiffFailed {
+        old_path: String,
+        new_path: String,
+        source: Box<Error>,
+    },
+
+    #[snafu(display("Failed to sync '{src_path}' to '{dest_path}': {source}"))]
+    SyncFailed {
+        src_path: String,
+        dest_path: String,
+        source: Box<Error>,
+    },
+
     #[snafu(display("Failed to list directory '{path}': {source}"))]
     ListDirectoryFailed { path: String, source: Box<Error> },
 
@@ -76,6 +145,19 @@ pub enum Error {
     #[snafu(display("Failed to read file '{path}': {source}"))]
     CatFailed { path: String, source: Box<Error> },
 
+    #[snafu(display("Failed to verify '{local_path}' against '{remote_path}': {source}"))]
+    VerifyFailed {
+        local_path: String,
+        remote_path: String,
+        source: Box<Error>,
+    },
+
+    #[snafu(display("Failed to find in '{path}': {source}"))]
+    FindFailed { path: String, source: Box<Error> },
+
+    #[snafu(display("Failed to checksum '{path}': {source}"))]
+    ChecksumFailed { path: String, source: Box<Error> },
+
     #[snafu(display("OpenDAL error: {source}"))]
     OpenDal { source: opendal::Error },
 
@@ -86,6 +168,77 @@ pub enum Error {
     Json { source: serde_json::Error },
 }
 
+impl Error {
+    /// Short name of what went wrong, looking through the variants wrapping another
+    /// error: the kind of OpenDAL and I/O errors (e.g. `NotFound`), otherwise the
+    /// name of the variant.
+    ///
+    /// This labels failures in reports only; code deciding what to do about an error
+    /// matches its variant (and the `opendal::ErrorKind` of `Error::OpenDal`) instead.
+    pub fn kind(&self) -> String {
+        let kind = match self {
+            Error::OpenDal { source } => return source.kind().to_string(),
+            Error::Io { source } => return format!("{:?}", source.kind()),
+            Error::MissingEnvVar { .. } => "MissingEnvVar",
+            Error::MissingProfileValue { .. } => "MissingProfileValue",
+            Error::ProfileNotFound { .. } => "ProfileNotFound",
+            Error::InvalidConfig { .. } => "InvalidConfig",
+            Error::UnsupportedProvider { .. } => "UnsupportedProvider",
+            Error::PathNotFound { .. } => "NotFound",
+            Error::InvalidPath { .. } => "InvalidPath",
+            Error::NoGlobMatch { .. } => "NoGlobMatch",
+            Error::AmbiguousGlob { .. } => "AmbiguousGlob",
+            Error::TargetNotDirectory { .. } => "TargetNotDirectory",
+            Error::DirectoryDeletionNotRecursive { .. } => "DirectoryDeletionNotRecursive",
+            Error::DirectoryUploadNotRecursive => "DirectoryUploadNotRecursive",
+            Error::PartialDeletion { .. } => "PartialDeletion",
+            Error::PartialTransfer { .. } => "PartialTransfer",
+            Error::InvalidArgument { .. } => "InvalidArgument",
+            Error::IncompleteTransfer { .. } => "IncompleteTransfer",
+            Error::TreeMismatch { .. } => "TreeMismatch",
+            Error::ChecksumMismatch { .. } => "ChecksumMismatch",
+            Error::Json { .. } => "Json",
+            _ => match self.wrapped() {
+                Some(source) => return source.kind(),
+                None => "Other",
+            },
+        };
+        kind.to_string()
+    }
+
+    /// The paths a partially failed operation could not handle, if that is what
+    /// this error (or the error it wraps) reports.
+    pub fn failures(&self) -> Option<&[Failure]> {
+        match self {
+            Error::PartialDeletion { failures } | Error::PartialTransfer { failures } => {
+                Some(failures)
+            }
+            _ => self.wrapped().and_then(Error::failures),
+        }
+    }
+
+    /// The error wrapped by variants adding the context of an operation.
+    fn wrapped(&self) -> Option<&Error> {
+        match self {
+            Error::DeleteFailed { source, .. }
+            | Error::DownloadFailed { source, .. }
+            | Error::UploadFailed { source, .. }
+            | Error::CopyFailed { source, .. }
+            | Error::MoveFailed { source, .. }
+            | Error::DiffFailed { source, .. }
+            | Error::SyncFailed { source, .. }
+            | Error::ListDirectoryFailed { source, .. }
+            | Error::DiskUsageFailed { source, .. }
+            | Error::DirectoryCreationFailed { source, .. }
+            | Error::CatFailed { source, .. }
+            | Error::VerifyFailed { source, .. }
+            | Error::FindFailed { source, .. }
+            | Error::ChecksumFailed { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
 impl From<opendal::Error> for Error {
     fn from(error: opendal::Error) -> Self {
         Error::OpenDal { source: error }
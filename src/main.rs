@@ -2,14 +2,14 @@ use clap::Parser;
 
 use storify::cli;
 use storify::error::Result;
-use storify::storage::StorageClient;
+use storify::storage::location::ClientResolver;
 
 use storify::cli::Args;
-use storify::config::load_storage_config;
+use storify::config::ConfigLoader;
 
 #[tokio::main]
 async fn main() {
-    let args = Args::parse();
+    let args = Args::parse_from(cli::normalize_args(std::env::args_os()));
 
     if let Err(e) = run_app(args).await {
         eprintln!("Error: {e}");
@@ -17,9 +17,13 @@ async fn main() {
     }
 }
 
-async fn run_app(args: Args) -> Result<()> {
-    let config = load_storage_config()?;
-    let client = StorageClient::new(config).await?;
-    cli::run(args, client).await?;
+async fn run_app(mut args: Args) -> Result<()> {
+    args.clear_failures_file()?;
+    let loader = ConfigLoader::new(args.config.as_deref(), args.profile.as_deref())?
+        .with_overrides(args.request_overrides());
+    if args.limit_rate.is_none() {
+        args.limit_rate = loader.limit_rate()?;
+    }
+    cli::run(args, ClientResolver::new(loader)).await?;
     Ok(())
 }
@@ -1,28 +1,60 @@
 use crate::error::{Error, Result};
+use crate::storage::constants::{
+    DEFAULT_IO_TIMEOUT_SECS, DEFAULT_RETRIES, DEFAULT_TIMEOUT_SECS, RETRY_MAX_DELAY_SECS,
+    RETRY_MIN_DELAY_MS,
+};
 use opendal::Operator;
+use opendal::layers::{RetryLayer, TimeoutLayer};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 pub mod constants;
+pub mod location;
 mod operations;
 mod utils;
+pub use self::operations::diff::{DiffEntry, DiffSummary, ModifiedEntry, TreeDiff};
+pub use self::operations::find::{Comparison, EntryKind, FindPredicates};
+pub use self::operations::stat::ObjectMeta;
+pub use self::operations::sync::SyncReport;
+pub use self::operations::usage::UsageReport;
+pub use self::operations::verify::{FileDifference, VerifyReport};
 pub use self::utils::OutputFormat;
+pub use self::utils::checksum::ChecksumAlgo;
+pub use self::utils::dry_run::DryRun;
+pub use self::utils::filter::PathFilter;
+pub use self::utils::options::TransferOptions;
+pub use self::utils::progress::{ConsoleProgress, Progress, ProgressEvent, ProgressObserver};
+pub use self::utils::report::{
+    Action, ChecksumEntry, Failure, Item, ItemObserver, ItemStatus, ListEntry, PathRecord,
+    Reporter, TransferMethod, TransferRecord, TransferReport, UsageEntry,
+};
+pub use self::utils::size::{format_size, parse_size};
+pub use self::utils::throttle::RateLimiter;
 
 use self::operations::cat::OpenDalFileReader;
+use self::operations::checksum::OpenDalChecksummer;
 use self::operations::copy::OpenDalCopier;
 use self::operations::delete::OpenDalDeleter;
+use self::operations::diff::OpenDalDiffer;
 use self::operations::download::OpenDalDownloader;
+use self::operations::find::OpenDalFinder;
 use self::operations::list::OpenDalLister;
 use self::operations::mkdir::OpenDalMkdirer;
 use self::operations::mv::OpenDalMover;
+use self::operations::sync::OpenDalSyncer;
 use self::operations::upload::OpenDalUploader;
 use self::operations::usage::OpenDalUsageCalculator;
+use self::operations::verify::OpenDalVerifier;
 use self::operations::{
-    Cater, Copier, Deleter, Downloader, Lister, Mkdirer, Mover, Stater, Uploader, UsageCalculator,
+    Cater, Checksummer, Copier, Deleter, Differ, Downloader, Finder, Lister, Mkdirer, Mover,
+    Stater, Syncer, Uploader, UsageCalculator, Verifier,
 };
+use self::utils::backend::Backend;
 use crate::wrap_err;
 
 /// Storage provider types
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum StorageProvider {
     Oss,
     S3,
@@ -48,6 +80,27 @@ impl FromStr for StorageProvider {
     }
 }
 
+/// Retry and timeout settings applied to every request of a client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestOptions {
+    /// Retries of a request failing with a temporary error; 0 disables retrying
+    pub retries: usize,
+    /// Timeout of a whole non-IO request such as stat, list or delete
+    pub timeout: Duration,
+    /// Timeout of a single read or write within a transfer
+    pub io_timeout: Duration,
+}
+
+impl Default for RequestOptions {
+    fn default() -> Self {
+        Self {
+            retries: DEFAULT_RETRIES,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            io_timeout: Duration::from_secs(DEFAULT_IO_TIMEOUT_SECS),
+        }
+    }
+}
+
 /// Unified storage configuration for different providers
 #[derive(Debug, Clone)]
 pub struct StorageConfig {
@@ -59,6 +112,7 @@ pub struct StorageConfig {
     pub region: Option<String>,
     pub root_path: Option<String>,
     pub name_node: Option<String>,
+    pub requests: RequestOptions,
 }
 
 impl StorageConfig {
@@ -77,6 +131,7 @@ impl StorageConfig {
             region,
             root_path: None,
             name_node: None,
+            requests: RequestOptions::default(),
         }
     }
 
@@ -95,6 +150,7 @@ impl StorageConfig {
             region,
             root_path: None,
             name_node: None,
+            requests: RequestOptions::default(),
         }
     }
 
@@ -113,6 +169,7 @@ impl StorageConfig {
             region,
             root_path: None,
             name_node: None,
+            requests: RequestOptions::default(),
         }
     }
 
@@ -126,6 +183,7 @@ impl StorageConfig {
             region: None,
             root_path: Some(root_path),
             name_node: None,
+            requests: RequestOptions::default(),
         }
     }
 
@@ -139,6 +197,7 @@ impl StorageConfig {
             region: None,
             root_path: Some(root_path),
             name_node: Some(name_node),
+            requests: RequestOptions::default(),
         }
     }
 }
@@ -146,28 +205,94 @@ impl StorageConfig {
 /// Unified storage client using OpenDAL
 #[derive(Clone)]
 pub struct StorageClient {
-    operator: Operator,
+    backend: Backend,
     provider: StorageProvider,
+    progress: Progress,
 }
 
 impl StorageClient {
+    /// Create a client for `config` that shows the progress of its transfers on stderr
+    /// (`ConsoleProgress`), the default implementation.
+    ///
+    /// Use `StorageClient::quiet` for a client that reports no progress, or
+    /// `with_progress_observer` to report it elsewhere.
     pub async fn new(config: StorageConfig) -> Result<Self> {
-        let operator = Self::build_operator(&config)?;
         Ok(Self {
-            operator,
+            progress: Progress::console(),
+            ..Self::quiet(config).await?
+        })
+    }
+
+    /// Create a client for `config` that reports no progress.
+    pub async fn quiet(config: StorageConfig) -> Result<Self> {
+        let service = Self::build_service(&config)?;
+        let operator = Self::apply_layers(service.clone(), &config.requests);
+        let server_side = Self::apply_retries(service, &config.requests);
+        Ok(Self {
+            backend: Backend::new(operator, server_side, &config),
             provider: config.provider,
+            progress: Progress::default(),
         })
     }
 
+    /// Send the progress events of this client's transfers to `observer` instead of
+    /// the console.
+    ///
+    /// Transfers whose `TransferOptions` bring a progress observer of their own report
+    /// to that one instead.
+    pub fn with_progress_observer(self, observer: Arc<dyn ProgressObserver>) -> Self {
+        Self {
+            progress: Progress::new(observer),
+            ..self
+        }
+    }
+
     pub fn provider(&self) -> StorageProvider {
         self.provider
     }
 
     pub fn operator(&self) -> &Operator {
-        &self.operator
+        &self.backend.operator
+    }
+
+    /// `options`, reporting progress to this client's observer unless they set their own.
+    fn transfer_options(&self, options: &TransferOptions) -> TransferOptions {
+        let mut options = options.clone();
+        if !options.progress.is_enabled() {
+            options.progress = self.progress.clone();
+        }
+        options
+    }
+
+    /// Wrap every request in a timeout, retrying those that fail with a temporary error.
+    ///
+    /// Timeouts are applied below retries, so that a request that timed out is retried
+    /// as well. Permanent errors such as `NotFound` or `PermissionDenied` fail at once.
+    fn apply_layers(operator: Operator, requests: &RequestOptions) -> Operator {
+        let operator = operator.layer(
+            TimeoutLayer::new()
+                .with_timeout(requests.timeout)
+                .with_io_timeout(requests.io_timeout),
+        );
+        Self::apply_retries(operator, requests)
+    }
+
+    /// Retry requests that fail with a temporary error, without limiting their duration.
+    fn apply_retries(operator: Operator, requests: &RequestOptions) -> Operator {
+        if requests.retries == 0 {
+            return operator;
+        }
+        operator.layer(
+            RetryLayer::new()
+                .with_max_times(requests.retries)
+                .with_min_delay(Duration::from_millis(RETRY_MIN_DELAY_MS))
+                .with_max_delay(Duration::from_secs(RETRY_MAX_DELAY_SECS))
+                .with_jitter()
+                .with_notify(log_retry),
+        )
     }
 
-    fn build_operator(config: &StorageConfig) -> Result<Operator> {
+    fn build_service(config: &StorageConfig) -> Result<Operator> {
         match &config.provider {
             StorageProvider::Oss => {
                 let mut builder = opendal::services::Oss::default().bucket(&config.bucket);
@@ -249,33 +374,53 @@ impl StorageClient {
         }
     }
 
-    pub async fn list_directory(&self, path: &str, long: bool, recursive: bool) -> Result<()> {
+    pub async fn list_directory(
+        &self,
+        path: &str,
+        recursive: bool,
+        filter: &PathFilter,
+        reporter: &Reporter,
+    ) -> Result<Vec<ListEntry>> {
         log::debug!(
-            "list_directory provider={:?} path={} long={} recursive={}",
+            "list_directory provider={:?} path={} recursive={}",
             self.provider,
             path,
-            long,
             recursive
         );
-        let lister = OpenDalLister::new(self.operator.clone());
+        let lister = OpenDalLister::new(
+            self.backend.operator.clone(),
+            filter.clone(),
+            reporter.clone(),
+        );
         wrap_err!(
-            lister.list(path, long, recursive).await,
+            lister.list(path, recursive).await,
             ListDirectoryFailed {
                 path: path.to_string()
             }
         )
     }
 
-    pub async fn download_files(&self, remote_path: &str, local_path: &str) -> Result<()> {
+    pub async fn download_files(
+        &self,
+        remote_path: &str,
+        local_path: &str,
+        options: &TransferOptions,
+    ) -> Result<TransferReport> {
         log::debug!(
             "download_files provider={:?} remote_path={} local_path={}",
             self.provider,
             remote_path,
             local_path
         );
-        let downloader = OpenDalDownloader::new(self.operator.clone());
+        let downloader = OpenDalDownloader::new(
+            self.backend.operator.clone(),
+            self.transfer_options(options),
+        );
+        let result = downloader.download(remote_path, local_path).await;
         wrap_err!(
-            downloader.download(remote_path, local_path).await,
+            options
+                .reporter
+                .transfer_result(result, Action::Download, remote_path, local_path),
             DownloadFailed {
                 remote_path: remote_path.to_string(),
                 local_path: local_path.to_string()
@@ -283,14 +428,24 @@ impl StorageClient {
         )
     }
 
-    pub async fn disk_usage(&self, path: &str, summary: bool) -> Result<()> {
+    pub async fn disk_usage(
+        &self,
+        path: &str,
+        summary: bool,
+        filter: &PathFilter,
+        reporter: &Reporter,
+    ) -> Result<UsageReport> {
         log::debug!(
             "disk_usage provider={:?} path={} summary={}",
             self.provider,
             path,
             summary
         );
-        let calculator = OpenDalUsageCalculator::new(self.operator.clone());
+        let calculator = OpenDalUsageCalculator::new(
+            self.backend.operator.clone(),
+            filter.clone(),
+            reporter.clone(),
+        );
         wrap_err!(
             calculator.calculate_usage(path, summary).await,
             DiskUsageFailed {
@@ -304,7 +459,8 @@ impl StorageClient {
         local_path: &str,
         remote_path: &str,
         is_recursive: bool,
-    ) -> Result<()> {
+        options: &TransferOptions,
+    ) -> Result<TransferReport> {
         log::debug!(
             "upload_files provider={:?} local_path={} remote_path={} recursive={}",
             self.provider,
@@ -312,9 +468,15 @@ impl StorageClient {
             remote_path,
             is_recursive
         );
-        let uploader = OpenDalUploader::new(self.operator.clone());
+        let uploader = OpenDalUploader::new(
+            self.backend.operator.clone(),
+            self.transfer_options(options),
+        );
+        let result = uploader.upload(local_path, remote_path, is_recursive).await;
         wrap_err!(
-            uploader.upload(local_path, remote_path, is_recursive).await,
+            options
+                .reporter
+                .transfer_result(result, Action::Upload, local_path, remote_path),
             UploadFailed {
                 local_path: local_path.to_string(),
                 remote_path: remote_path.to_string()
@@ -322,14 +484,27 @@ impl StorageClient {
         )
     }
 
-    pub async fn delete_files(&self, paths: &[String], recursive: bool) -> Result<()> {
+    pub async fn delete_files(
+        &self,
+        paths: &[String],
+        recursive: bool,
+        filter: &PathFilter,
+        dry_run: Option<&DryRun>,
+        reporter: &Reporter,
+    ) -> Result<Vec<PathRecord>> {
         log::debug!(
-            "delete_files provider={:?} paths_count={} recursive={}",
+            "delete_files provider={:?} paths_count={} recursive={} dry_run={}",
             self.provider,
             paths.len(),
-            recursive
+            recursive,
+            dry_run.is_some()
+        );
+        let deleter = OpenDalDeleter::new(
+            self.backend.operator.clone(),
+            filter.clone(),
+            dry_run.cloned(),
+            reporter.clone(),
         );
-        let deleter = OpenDalDeleter::new(self.operator.clone());
         wrap_err!(
             deleter.delete(paths, recursive).await,
             DeleteFailed {
@@ -340,16 +515,40 @@ impl StorageClient {
         )
     }
 
-    pub async fn copy_files(&self, src_path: &str, dest_path: &str) -> Result<()> {
+    pub async fn copy_files(
+        &self,
+        src_path: &str,
+        dest_path: &str,
+        options: &TransferOptions,
+    ) -> Result<TransferReport> {
+        self.copy_files_to(self, src_path, dest_path, options).await
+    }
+
+    /// Copy files from this client's storage to `dest`, which may be another provider or bucket.
+    pub async fn copy_files_to(
+        &self,
+        dest: &StorageClient,
+        src_path: &str,
+        dest_path: &str,
+        options: &TransferOptions,
+    ) -> Result<TransferReport> {
         log::debug!(
-            "copy_files provider={:?} src_path={} dest_path={}",
+            "copy_files src_provider={:?} dest_provider={:?} src_path={} dest_path={}",
             self.provider,
+            dest.provider,
             src_path,
             dest_path
         );
-        let copier = OpenDalCopier::new(self.operator.clone());
+        let copier = OpenDalCopier::new(
+            self.backend.clone(),
+            dest.backend.clone(),
+            self.transfer_options(options),
+        );
+        let result = copier.copy(src_path, dest_path).await;
         wrap_err!(
-            copier.copy(src_path, dest_path).await,
+            options
+                .reporter
+                .transfer_result(result, Action::Copy, src_path, dest_path),
             CopyFailed {
                 src_path: src_path.to_string(),
                 dest_path: dest_path.to_string()
@@ -357,16 +556,40 @@ impl StorageClient {
         )
     }
 
-    pub async fn move_files(&self, src_path: &str, dest_path: &str) -> Result<()> {
+    pub async fn move_files(
+        &self,
+        src_path: &str,
+        dest_path: &str,
+        options: &TransferOptions,
+    ) -> Result<TransferReport> {
+        self.move_files_to(self, src_path, dest_path, options).await
+    }
+
+    /// Move files from this client's storage to `dest`, which may be another provider or bucket.
+    pub async fn move_files_to(
+        &self,
+        dest: &StorageClient,
+        src_path: &str,
+        dest_path: &str,
+        options: &TransferOptions,
+    ) -> Result<TransferReport> {
         log::debug!(
-            "move_files provider={:?} src_path={} dest_path={}",
+            "move_files src_provider={:?} dest_provider={:?} src_path={} dest_path={}",
             self.provider,
+            dest.provider,
             src_path,
             dest_path
         );
-        let mover = OpenDalMover::new(self.operator.clone());
+        let mover = OpenDalMover::new(
+            self.backend.clone(),
+            dest.backend.clone(),
+            self.transfer_options(options),
+        );
+        let result = mover.mover(src_path, dest_path).await;
         wrap_err!(
-            mover.mover(src_path, dest_path).await,
+            options
+                .reporter
+                .transfer_result(result, Action::Move, src_path, dest_path),
             MoveFailed {
                 src_path: src_path.to_string(),
                 dest_path: dest_path.to_string()
@@ -374,100 +597,261 @@ impl StorageClient {
         )
     }
 
-    pub async fn create_directory(&self, path: &str, parents: bool) -> Result<()> {
+    /// Mirror `src_path` on this client's storage into `dest_path` on `dest`,
+    /// transferring only new or changed files.
+    pub async fn sync_to(
+        &self,
+        dest: &StorageClient,
+        src_path: &str,
+        dest_path: &str,
+        delete: bool,
+        options: &TransferOptions,
+    ) -> Result<SyncReport> {
         log::debug!(
-            "create_directory provider={:?} path={} parents={}",
+            "sync src_provider={:?} dest_provider={:?} src_path={} dest_path={} delete={} dry_run={}",
+            self.provider,
+            dest.provider,
+            src_path,
+            dest_path,
+            delete,
+            options.dry_run.is_some()
+        );
+        let syncer = OpenDalSyncer::new(
+            self.backend.clone(),
+            dest.backend.clone(),
+            self.transfer_options(options),
+        );
+        wrap_err!(
+            syncer.sync(src_path, dest_path, delete).await,
+            SyncFailed {
+                src_path: src_path.to_string(),
+                dest_path: dest_path.to_string()
+            }
+        )
+    }
+
+    /// Compare `old_path` on this client's storage with `new_path` on `other`.
+    ///
+    /// Files only in the second tree are listed as added, files only in the first as
+    /// removed.
+    pub async fn diff_with(
+        &self,
+        other: &StorageClient,
+        old_path: &str,
+        new_path: &str,
+        filter: &PathFilter,
+    ) -> Result<TreeDiff> {
+        log::debug!(
+            "diff old_provider={:?} new_provider={:?} old_path={} new_path={}",
+            self.provider,
+            other.provider,
+            old_path,
+            new_path
+        );
+        let differ = OpenDalDiffer::new(
+            self.backend.operator.clone(),
+            other.backend.operator.clone(),
+            filter.clone(),
+        );
+        wrap_err!(
+            differ.diff(old_path, new_path).await,
+            DiffFailed {
+                old_path: old_path.to_string(),
+                new_path: new_path.to_string()
+            }
+        )
+    }
+
+    pub async fn create_directory(
+        &self,
+        path: &str,
+        parents: bool,
+        dry_run: Option<&DryRun>,
+        reporter: &Reporter,
+    ) -> Result<Vec<PathRecord>> {
+        log::debug!(
+            "create_directory provider={:?} path={} parents={} dry_run={}",
             self.provider,
             path,
-            parents
+            parents,
+            dry_run.is_some()
+        );
+        let mkdirer = OpenDalMkdirer::new(
+            self.backend.operator.clone(),
+            dry_run.cloned(),
+            reporter.clone(),
         );
-        let mkdirer = OpenDalMkdirer::new(self.operator.clone());
+        let result = mkdirer.mkdir(path, parents).await;
         wrap_err!(
-            mkdirer.mkdir(path, parents).await,
+            reporter.path_result(result, Action::Mkdir, path),
             DirectoryCreationFailed {
                 path: path.to_string()
             }
         )
     }
 
-    pub async fn cat_file(&self, path: &str, force: bool, size_limit_mb: u64) -> Result<()> {
+    /// Size in bytes of a file to be read with `cat_file`.
+    pub async fn cat_size(&self, path: &str) -> Result<u64> {
+        log::debug!("cat_size provider={:?} path={}", self.provider, path);
+        let reader = OpenDalFileReader::new(self.backend.operator.clone(), None);
+        wrap_err!(
+            reader.size(path).await,
+            CatFailed {
+                path: path.to_string()
+            }
+        )
+    }
+
+    /// Stream the contents of a file to `out`, returning the number of bytes written.
+    ///
+    /// With a `rate_limit`, reading is slowed down to stay under it.
+    pub async fn cat_file<W: std::io::Write>(
+        &self,
+        path: &str,
+        out: &mut W,
+        rate_limit: Option<&RateLimiter>,
+    ) -> Result<u64> {
+        log::debug!("cat_file provider={:?} path={}", self.provider, path);
+        let reader = OpenDalFileReader::new(self.backend.operator.clone(), rate_limit.cloned());
+        wrap_err!(
+            reader.cat(path, out).await,
+            CatFailed {
+                path: path.to_string()
+            }
+        )
+    }
+
+    /// Compute content checksums of a file or of every file under a directory prefix.
+    ///
+    /// With `relative`, paths under a directory prefix are given relative to it.
+    pub async fn checksum_files(
+        &self,
+        path: &str,
+        algo: ChecksumAlgo,
+        relative: bool,
+        filter: &PathFilter,
+        reporter: &Reporter,
+    ) -> Result<Vec<ChecksumEntry>> {
         log::debug!(
-            "cat_file provider={:?} path={},force={},size_limit_mb={}",
+            "checksum_files provider={:?} path={} algo={} relative={}",
             self.provider,
             path,
-            force,
-            size_limit_mb
+            algo,
+            relative
+        );
+        let checksummer = OpenDalChecksummer::new(
+            self.backend.operator.clone(),
+            filter.clone(),
+            reporter.clone(),
         );
-        let reader = OpenDalFileReader::new(self.operator.clone());
         wrap_err!(
-            reader.cat(path, force, size_limit_mb).await,
-            CatFailed {
+            checksummer.checksum(path, algo, relative).await,
+            ChecksumFailed {
                 path: path.to_string()
             }
         )
     }
 
-    pub async fn stat_metadata(&self, path: &str, format: OutputFormat) -> Result<()> {
+    /// Compare a local directory tree with a remote prefix.
+    ///
+    /// Differences are part of the report; only failures to compare are errors.
+    pub async fn verify_tree(
+        &self,
+        local_path: &str,
+        remote_path: &str,
+        checksum: bool,
+        filter: &PathFilter,
+    ) -> Result<VerifyReport> {
         log::debug!(
-            "stat_metadata provider={:?} path={} format={:?}",
+            "verify_tree provider={:?} local={} remote={} checksum={}",
             self.provider,
-            path,
-            format
+            local_path,
+            remote_path,
+            checksum
         );
-        let stater = self::operations::stat::OpenDalStater::new(self.operator.clone());
-        let meta = stater.stat(path).await?;
-
-        match format {
-            OutputFormat::Human => {
-                println!("path={}", meta.path);
-                println!("type={}", meta.entry_type);
-                println!("size={}", meta.size);
-                if let Some(t) = meta.last_modified {
-                    println!("last_modified={}", t);
-                }
-                if let Some(etag) = meta.etag {
-                    println!("etag=\"{}\"", etag);
-                }
-                if let Some(ct) = meta.content_type {
-                    println!("content_type={}", ct);
-                }
+        let verifier = OpenDalVerifier::new(self.backend.operator.clone(), filter.clone());
+        wrap_err!(
+            verifier.verify(local_path, remote_path, checksum).await,
+            VerifyFailed {
+                local_path: local_path.to_string(),
+                remote_path: remote_path.to_string()
             }
-            OutputFormat::Raw => {
-                println!("path={}", meta.path);
-                println!("type={}", meta.entry_type);
-                println!("size={}", meta.size);
-                if let Some(t) = meta.last_modified {
-                    println!("last_modified={}", t);
-                }
-                if let Some(etag) = meta.etag {
-                    println!("etag=\"{}\"", etag);
-                }
-                if let Some(ct) = meta.content_type {
-                    println!("content_type={}", ct);
-                }
+        )
+    }
+
+    /// Find the entries under `path` that satisfy every predicate.
+    pub async fn find_paths(&self, path: &str, predicates: &FindPredicates) -> Result<Vec<String>> {
+        log::debug!(
+            "find_paths provider={:?} path={} predicates={:?}",
+            self.provider,
+            path,
+            predicates
+        );
+        let finder = OpenDalFinder::new(self.backend.operator.clone(), None, Reporter::default());
+        wrap_err!(
+            finder.find(path, predicates).await,
+            FindFailed {
+                path: path.to_string()
             }
-            OutputFormat::Json => {
-                #[derive(serde::Serialize)]
-                struct JsonMeta<'a> {
-                    path: &'a str,
-                    entry_type: &'a str,
-                    size: u64,
-                    last_modified: Option<String>,
-                    etag: Option<String>,
-                    content_type: Option<String>,
-                }
-                let json = JsonMeta {
-                    path: &meta.path,
-                    entry_type: &meta.entry_type,
-                    size: meta.size,
-                    last_modified: meta.last_modified,
-                    etag: meta.etag,
-                    content_type: meta.content_type,
-                };
-                println!("{}", serde_json::to_string(&json)?);
+        )
+    }
+
+    /// Delete entries returned by `find_paths`, keeping non-empty directories.
+    pub async fn delete_found(
+        &self,
+        paths: &[String],
+        dry_run: Option<&DryRun>,
+        reporter: &Reporter,
+    ) -> Result<Vec<PathRecord>> {
+        log::debug!(
+            "delete_found provider={:?} paths_count={} dry_run={}",
+            self.provider,
+            paths.len(),
+            dry_run.is_some()
+        );
+        let finder = OpenDalFinder::new(
+            self.backend.operator.clone(),
+            dry_run.cloned(),
+            reporter.clone(),
+        );
+        wrap_err!(
+            finder.delete_found(paths).await,
+            DeleteFailed {
+                paths: paths.iter().take(5).cloned().collect::<Vec<_>>().join(","),
+                recursive: false
             }
-        }
+        )
+    }
 
-        Ok(())
+    /// Expand a glob pattern (`*`, `?`, `[...]`, `**`) into the matching paths.
+    ///
+    /// Paths without metacharacters are returned unchanged, without checking they exist.
+    pub async fn expand_glob(&self, pattern: &str) -> Result<Vec<String>> {
+        log::debug!(
+            "expand_glob provider={:?} pattern={}",
+            self.provider,
+            pattern
+        );
+        self::utils::glob::expand_glob(&self.backend.operator, pattern).await
+    }
+
+    /// Whether `path` is an existing directory, or a prefix with entries below it.
+    pub async fn is_directory(&self, path: &str) -> bool {
+        self::utils::transfer::is_directory(&self.backend.operator, path).await
     }
+
+    pub async fn stat_metadata(&self, path: &str) -> Result<ObjectMeta> {
+        log::debug!("stat_metadata provider={:?} path={}", self.provider, path);
+        let stater = self::operations::stat::OpenDalStater::new(self.backend.operator.clone());
+        stater.stat(path).await
+    }
+}
+
+/// Log a retried request at debug level, instead of the warning opendal logs by default.
+fn log_retry(err: &opendal::Error, delay: Duration) {
+    log::debug!(
+        "Retrying request in {:.1}s after error: {}",
+        delay.as_secs_f64(),
+        err
+    );
 }
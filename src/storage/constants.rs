@@ -1,11 +1,43 @@
 // Buffer related constants
-pub const DEFAULT_BUFFER_SIZE: usize = 8192;
 pub const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024;
 
-// Progress related constants
-// Controls how often progress is printed (in multiples of buffer size)
-pub const PROGRESS_UPDATE_INTERVAL: u64 = 100;
+// Concurrency related constants
+// Default number of files transferred in parallel by recursive operations
+pub const DEFAULT_JOBS: usize = 8;
+
+// Multipart transfer related constants
+// Files larger than the part size are uploaded or downloaded as concurrent parts
+pub const DEFAULT_PART_SIZE: u64 = 8 * 1024 * 1024;
+// Smallest part accepted by S3-compatible services (except for the last part)
+pub const MIN_PART_SIZE: u64 = 5 * 1024 * 1024;
+// Largest number of parts in a single S3-compatible multipart upload
+pub const MAX_PART_COUNT: u64 = 10_000;
+pub const DEFAULT_PART_CONCURRENCY: usize = 4;
+// Largest object S3 and COS copy server-side in a single request
+pub const MAX_COPY_OBJECT_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+// Largest object OSS copies server-side in a single request
+pub const MAX_OSS_COPY_OBJECT_SIZE: u64 = 1024 * 1024 * 1024;
+
+// Request related constants
+// Retries of a request failing with a temporary error (5xx, throttling, timeouts)
+pub const DEFAULT_RETRIES: usize = 3;
+// Timeout of a whole non-IO request such as stat, list or delete, in seconds; server-side
+// copies and renames are exempt, since they take longer the larger the object
+pub const DEFAULT_TIMEOUT_SECS: u64 = 60;
+// Timeout of a single read or write within a transfer, in seconds
+pub const DEFAULT_IO_TIMEOUT_SECS: u64 = 30;
+// Delay before the first retry of a request, in milliseconds; doubled for every further retry
+pub const RETRY_MIN_DELAY_MS: u64 = 1000;
+// Longest delay between two retries of a request, in seconds
+pub const RETRY_MAX_DELAY_SECS: u64 = 60;
 
 // Filesystem default
 pub const DEFAULT_FS_ROOT: &str = "./storage";
 pub const CAT_CONFIRM_SIZE_THRESHOLD: u64 = 10 * 1024 * 1024;
+
+// Config file location (relative to the user config directory)
+pub const CONFIG_DIR_NAME: &str = "storify";
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+// Resume state location (relative to the user state directory's storify directory)
+pub const RESUME_DIR_NAME: &str = "resume";
@@ -0,0 +1,174 @@
+// Parsing of user supplied remote paths and resolution to storage clients
+use crate::config::ConfigLoader;
+use crate::error::{Error, Result};
+use crate::storage::{ProgressObserver, StorageClient};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// The storage backend a path argument refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StorageTarget {
+    /// The client configured from the environment and active profile (bare paths).
+    Default,
+    /// A bucket on an explicit provider, e.g. `s3://bucket/key`.
+    Bucket { provider: String, bucket: String },
+    /// The local filesystem, e.g. `fs:///tmp/root/key`.
+    LocalFs,
+    /// A named profile from the config file, e.g. `prod:/key`.
+    Profile(String),
+}
+
+/// A remote path argument split into its storage target and the key within it.
+///
+/// Accepted forms:
+/// - `oss://bucket/key`, `s3://bucket/key`, `cos://bucket/key`
+/// - `fs:///absolute/path` or `fs://relative/path`
+/// - `profile-name:/key` for any profile defined in the config file
+/// - anything else is a bare key served by the default client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoragePath {
+    pub target: StorageTarget,
+    pub path: String,
+}
+
+impl StoragePath {
+    /// Parse a path argument.
+    ///
+    /// # Arguments
+    /// * `input` - The path as typed by the user
+    /// * `is_profile` - Whether a `name:` prefix names a configured profile
+    ///
+    /// # Returns
+    /// * `Result<StoragePath>` - The parsed target and key
+    pub fn parse(input: &str, is_profile: impl Fn(&str) -> bool) -> Result<Self> {
+        if let Some((scheme, rest)) = input.split_once("://") {
+            return Self::parse_url(input, scheme, rest);
+        }
+
+        if let Some((name, rest)) = input.split_once(':')
+            && !name.is_empty()
+            && !name.contains('/')
+            && is_profile(name)
+        {
+            return Ok(Self {
+                target: StorageTarget::Profile(name.to_string()),
+                path: root_if_empty(rest),
+            });
+        }
+
+        Ok(Self {
+            target: StorageTarget::Default,
+            path: input.to_string(),
+        })
+    }
+
+    fn parse_url(input: &str, scheme: &str, rest: &str) -> Result<Self> {
+        let scheme = scheme.to_ascii_lowercase();
+        match scheme.as_str() {
+            "oss" | "s3" | "cos" => {
+                let (bucket, key) = rest.split_once('/').unwrap_or((rest, ""));
+                if bucket.is_empty() {
+                    return Err(Error::InvalidPath {
+                        path: input.to_string(),
+                    });
+                }
+                Ok(Self {
+                    target: StorageTarget::Bucket {
+                        provider: scheme,
+                        bucket: bucket.to_string(),
+                    },
+                    path: root_if_empty(key),
+                })
+            }
+            "fs" => {
+                if rest.is_empty() {
+                    return Err(Error::InvalidPath {
+                        path: input.to_string(),
+                    });
+                }
+                let mut path = std::path::absolute(Path::new(rest))?
+                    .to_string_lossy()
+                    .to_string();
+                if rest.ends_with('/') && !path.ends_with('/') {
+                    path.push('/');
+                }
+                Ok(Self {
+                    target: StorageTarget::LocalFs,
+                    path,
+                })
+            }
+            _ => Err(Error::UnsupportedProvider { provider: scheme }),
+        }
+    }
+}
+
+/// Map an empty key to the storage root.
+fn root_if_empty(key: &str) -> String {
+    if key.is_empty() {
+        "/".to_string()
+    } else {
+        key.to_string()
+    }
+}
+
+/// Resolves path arguments to the client serving them, building one client per target.
+pub struct ClientResolver {
+    loader: ConfigLoader,
+    clients: HashMap<StorageTarget, StorageClient>,
+    progress: Option<Arc<dyn ProgressObserver>>,
+}
+
+impl ClientResolver {
+    /// Create a resolver backed by the given configuration loader.
+    pub fn new(loader: ConfigLoader) -> Self {
+        Self {
+            loader,
+            clients: HashMap::new(),
+            progress: None,
+        }
+    }
+
+    /// Send the progress events of every client built from now on to `observer` instead
+    /// of the console.
+    pub fn with_progress_observer(self, observer: Arc<dyn ProgressObserver>) -> Self {
+        Self {
+            progress: Some(observer),
+            ..self
+        }
+    }
+
+    /// Parse a path argument without building a client.
+    pub fn parse(&self, input: &str) -> Result<StoragePath> {
+        StoragePath::parse(input, |name| self.loader.has_profile(name))
+    }
+
+    /// Resolve a path argument to its client and the key within that client.
+    pub async fn resolve(&mut self, input: &str) -> Result<(StorageClient, String)> {
+        let parsed = self.parse(input)?;
+        let client = self.client(&parsed.target).await?;
+        Ok((client, parsed.path))
+    }
+
+    /// Get (or lazily build) the client for a storage target.
+    pub async fn client(&mut self, target: &StorageTarget) -> Result<StorageClient> {
+        if let Some(client) = self.clients.get(target) {
+            return Ok(client.clone());
+        }
+
+        let config = match target {
+            StorageTarget::Default => self.loader.load()?,
+            StorageTarget::Bucket { provider, bucket } => {
+                self.loader.load_bucket(provider, bucket)?
+            }
+            StorageTarget::LocalFs => self.loader.load_local(),
+            StorageTarget::Profile(name) => self.loader.load_profile(name)?,
+        };
+        let mut client = StorageClient::new(config).await?;
+        if let Some(observer) = &self.progress {
+            client = client.with_progress_observer(observer.clone());
+        }
+        self.clients.insert(target.clone(), client.clone());
+        Ok(client)
+    }
+}
@@ -0,0 +1,100 @@
+use crate::error::{Error, Result};
+use crate::storage::utils::checksum::{ChecksumAlgo, compute_checksum};
+use crate::storage::utils::filter::PathFilter;
+use crate::storage::utils::report::{ChecksumEntry, Item, Reporter};
+use crate::storage::utils::transfer::is_directory;
+use crate::storage::utils::walk::collect_files;
+use opendal::{ErrorKind, Operator};
+use std::path::PathBuf;
+
+/// Trait for computing content checksums of stored objects.
+pub trait Checksummer {
+    /// Compute the checksum of a file, or of every file under a directory prefix.
+    ///
+    /// # Arguments
+    /// * `path` - File or directory prefix to checksum
+    /// * `algo` - Hash algorithm to use
+    /// * `relative` - Give paths relative to a directory prefix, as in a manifest
+    ///
+    /// # Returns
+    /// * `Result<Vec<ChecksumEntry>>` - The checksum of every file, in path order
+    async fn checksum(
+        &self,
+        path: &str,
+        algo: ChecksumAlgo,
+        relative: bool,
+    ) -> Result<Vec<ChecksumEntry>>;
+}
+
+/// Implementation of Checksummer for OpenDAL Operator.
+///
+/// Objects are streamed through the client, so the result is a true content hash even
+/// when the backend ETag is not (e.g. after a multipart upload).
+///
+/// Under a directory prefix, only files selected by `filter` are hashed. Every checksum
+/// is passed to `reporter` as soon as it is computed.
+pub struct OpenDalChecksummer {
+    operator: Operator,
+    filter: PathFilter,
+    reporter: Reporter,
+}
+
+impl OpenDalChecksummer {
+    /// Create a new checksummer with the given OpenDAL operator, file filter and reporter.
+    pub fn new(operator: Operator, filter: PathFilter, reporter: Reporter) -> Self {
+        Self {
+            operator,
+            filter,
+            reporter,
+        }
+    }
+
+    /// Report a checksum and add it to `entries`.
+    fn record(
+        &self,
+        entries: &mut Vec<ChecksumEntry>,
+        path: &str,
+        hash: String,
+        etag: Option<&str>,
+    ) {
+        let entry = ChecksumEntry {
+            path: path.to_string(),
+            hash,
+            etag: etag.map(|e| e.trim_matches('"').to_string()),
+        };
+        self.reporter.report(Item::Checksum(&entry));
+        entries.push(entry);
+    }
+}
+
+impl Checksummer for OpenDalChecksummer {
+    async fn checksum(
+        &self,
+        path: &str,
+        algo: ChecksumAlgo,
+        relative: bool,
+    ) -> Result<Vec<ChecksumEntry>> {
+        let mut entries = Vec::new();
+        if is_directory(&self.operator, path).await {
+            for (relative_path, meta) in collect_files(&self.operator, path, &self.filter).await? {
+                let hash = compute_checksum(&self.operator, &meta.path, algo).await?;
+                let shown = if relative { &relative_path } else { &meta.path };
+                self.record(&mut entries, shown, hash, meta.etag.as_deref());
+            }
+            return Ok(entries);
+        }
+
+        let meta = match self.operator.stat(path).await {
+            Ok(meta) => meta,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(Error::PathNotFound {
+                    path: PathBuf::from(path),
+                });
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let hash = compute_checksum(&self.operator, path, algo).await?;
+        self.record(&mut entries, path, hash, meta.etag());
+        Ok(entries)
+    }
+}
@@ -1,15 +1,17 @@
 use crate::error::{InvalidPathSnafu, Result};
-use crate::storage::constants::DEFAULT_CHUNK_SIZE;
-use crate::storage::utils::path::{
-    basename, build_remote_path, ensure_trailing_slash, get_root_relative_path,
+use crate::storage::utils::backend::Backend;
+use crate::storage::utils::options::TransferOptions;
+use crate::storage::utils::path::{basename, build_remote_path, get_root_relative_path};
+use crate::storage::utils::report::{Action, TransferMethod, TransferRecord, TransferReport};
+use crate::storage::utils::transfer::{
+    TransferTask, ensure_directory, is_directory, run_transfer, run_transfers, same_storage,
+    server_side_copy, stream_copy,
 };
-use crate::storage::utils::progress::ConsoleProgressReporter;
-use async_recursion::async_recursion;
 use futures::stream::TryStreamExt;
-use opendal::{EntryMode, Operator};
+use opendal::EntryMode;
 use snafu::ensure;
 
-/// Trait for copying files and directories within storage.
+/// Trait for copying files and directories between storage locations.
 pub trait Copier {
     /// Copy a single file or entire directory from one location to another in object storage.
     ///
@@ -18,49 +20,36 @@ pub trait Copier {
     /// * `dest_path` - Destination path in object storage
     ///
     /// # Returns
-    /// * `Result<()>` - Success or detailed error information
-    async fn copy(&self, src_path: &str, dest_path: &str) -> Result<()>;
+    /// * `Result<TransferReport>` - The copied files or detailed error information
+    async fn copy(&self, src_path: &str, dest_path: &str) -> Result<TransferReport>;
 }
 
-/// Implementation of Copier for OpenDAL Operator.
+/// Implementation of Copier for OpenDAL Operators.
+///
+/// Source and destination operators may point at different providers or buckets,
+/// in which case every byte is streamed through the client. Within a single storage
+/// that supports it, files are copied server-side instead.
 pub struct OpenDalCopier {
-    operator: Operator,
+    src: Backend,
+    dest: Backend,
+    options: TransferOptions,
 }
 
 impl OpenDalCopier {
-    /// Create a new copier with the given OpenDAL operator.
-    pub fn new(operator: Operator) -> Self {
-        Self { operator }
-    }
-
-    /// Hybrid directory detection for object storage: stat first; if not available, probe prefix.
-    async fn is_directory(&self, path: &str) -> bool {
-        match self.operator.stat(path).await.ok().map(|m| m.mode()) {
-            Some(EntryMode::DIR) => true,
-            Some(_) => false,
-            None => {
-                let probe = ensure_trailing_slash(path);
-                self.operator
-                    .list_with(&probe)
-                    .limit(1)
-                    .await
-                    .map(|entries| !entries.is_empty())
-                    .unwrap_or(false)
-            }
-        }
-    }
-
-    /// Ensure a remote directory exists (appends trailing '/').
-    async fn ensure_directory(&self, dir_path: &str) -> Result<()> {
-        let to_create = ensure_trailing_slash(dir_path);
-        self.operator.create_dir(&to_create).await?;
-        Ok(())
+    /// Create a new copier reading from `src` and writing to `dest`.
+    pub fn new(src: Backend, dest: Backend, options: TransferOptions) -> Self {
+        Self { src, dest, options }
     }
 
     /// Copy files recursively with directory structure preservation.
-    #[async_recursion]
-    async fn copy_file_recursive(&self, src_path: &str, dest_path: &str) -> Result<()> {
-        let lister = self.operator.lister_with(src_path).recursive(true).await?;
+    async fn copy_file_recursive(&self, src_path: &str, dest_path: &str) -> Result<TransferReport> {
+        let lister = self
+            .src
+            .operator
+            .lister_with(src_path)
+            .recursive(true)
+            .await?;
+        let mut tasks = Vec::new();
 
         let mut stream = lister;
         while let Some(entry) = stream.try_next().await? {
@@ -78,61 +67,74 @@ impl OpenDalCopier {
             let new_dest_path = build_remote_path(dest_path, &relative_path);
 
             if meta.mode() == EntryMode::DIR {
-                self.ensure_directory(&new_dest_path).await?;
-            } else {
-                self.stream_copy(entry_path, &new_dest_path).await?;
+                ensure_directory(&self.dest.operator, &new_dest_path, &self.options).await?;
+            } else if self.options.filter.matches(&relative_path) {
+                tasks.push(TransferTask::new(
+                    entry_path,
+                    new_dest_path,
+                    meta.content_length(),
+                ));
             }
         }
 
-        Ok(())
+        run_transfers(tasks, Action::Copy, &self.options, |task| async move {
+            self.copy_single(&task.src, &task.dest).await
+        })
+        .await
     }
 
-    /// Stream copy a single file with progress reporting.
-    async fn stream_copy(&self, src_path: &str, dest_path: &str) -> opendal::Result<()> {
-        let metadata = self.operator.stat(src_path).await?;
-        let file_size = metadata.content_length();
-
-        let mut writer = self.operator.writer(dest_path).await?;
-        let mut total_bytes = 0u64;
-        let mut offset = 0u64;
-
-        let reporter = ConsoleProgressReporter::new(
-            format!("Copying {src_path}"),
-            Some(file_size),
-            DEFAULT_CHUNK_SIZE as u64,
-        );
-
-        loop {
-            let chunk_size = std::cmp::min(DEFAULT_CHUNK_SIZE as u64, file_size - offset);
-
-            let data = self
-                .operator
-                .read_with(src_path)
-                .range(offset..offset + chunk_size)
-                .await?;
-            let data_len = data.len();
-            if data_len == 0 {
-                break;
-            }
-
-            writer.write(data).await?;
-            total_bytes += data_len as u64;
-            offset += chunk_size;
+    /// Whether files can be copied server-side instead of streamed.
+    fn server_side(&self) -> bool {
+        self.options.server_side
+            && same_storage(&self.src, &self.dest)
+            && self.src.operator.info().full_capability().copy
+    }
 
-            reporter.maybe_report(total_bytes);
+    /// Copy a single file, server-side when possible, otherwise streaming it while
+    /// tracking its progress. Files the service refuses to copy itself are streamed too.
+    ///
+    /// On a dry run the copy is only recorded.
+    async fn copy_single(&self, src_path: &str, dest_path: &str) -> Result<TransferRecord> {
+        if let Some(plan) = &self.options.dry_run {
+            let size = self.src.operator.stat(src_path).await?.content_length();
+            return Ok(plan.transfer(Action::Copy, src_path, dest_path, size));
+        }
+        if self.server_side()
+            && server_side_copy(&self.src, src_path, dest_path, self.options.verify).await?
+        {
+            return Ok(TransferRecord::done(
+                Action::Copy,
+                src_path,
+                dest_path,
+                None,
+                TransferMethod::ServerSideCopy,
+            ));
         }
 
-        writer.close().await?;
-        println!("\n✅ Copied: {src_path} → {dest_path} ({total_bytes} bytes)");
-
-        Ok(())
+        let total_bytes = stream_copy(
+            &self.src,
+            src_path,
+            &self.dest,
+            dest_path,
+            Action::Copy,
+            &self.options,
+            self.options.verify,
+        )
+        .await?;
+        Ok(TransferRecord::done(
+            Action::Copy,
+            src_path,
+            dest_path,
+            Some(total_bytes),
+            TransferMethod::Stream,
+        ))
     }
 }
 
 impl Copier for OpenDalCopier {
-    async fn copy(&self, src_path: &str, dest_path: &str) -> Result<()> {
-        let src_stat = self.operator.stat(src_path).await.ok();
-        let src_is_dir = self.is_directory(src_path).await;
+    async fn copy(&self, src_path: &str, dest_path: &str) -> Result<TransferReport> {
+        let src_stat = self.src.operator.stat(src_path).await.ok();
+        let src_is_dir = is_directory(&self.src.operator, src_path).await;
         ensure!(
             src_stat.is_some() || src_is_dir,
             InvalidPathSnafu {
@@ -141,21 +143,20 @@ impl Copier for OpenDalCopier {
         );
 
         if src_is_dir {
-            let target_root = if self.is_directory(dest_path).await {
+            let target_root = if is_directory(&self.dest.operator, dest_path).await {
                 let base_name = basename(src_path);
                 let target_root = build_remote_path(dest_path, &base_name);
-                self.ensure_directory(&target_root).await?;
+                ensure_directory(&self.dest.operator, &target_root, &self.options).await?;
                 target_root
             } else {
-                self.ensure_directory(dest_path).await?;
+                ensure_directory(&self.dest.operator, dest_path, &self.options).await?;
                 dest_path.to_string()
             };
 
-            self.copy_file_recursive(src_path, &target_root).await?;
-            Ok(())
+            self.copy_file_recursive(src_path, &target_root).await
         } else {
             let dest_is_dir_hint = dest_path.ends_with('/');
-            let dest_is_dir = self.is_directory(dest_path).await;
+            let dest_is_dir = is_directory(&self.dest.operator, dest_path).await;
 
             if dest_is_dir_hint && !dest_is_dir {
                 return Err(crate::error::Error::InvalidPath {
@@ -170,8 +171,8 @@ impl Copier for OpenDalCopier {
                 dest_path.to_string()
             };
 
-            self.stream_copy(src_path, &final_dest).await?;
-            Ok(())
+            let transfer = self.copy_single(src_path, &final_dest);
+            run_transfer(&self.options, src_path, transfer).await
         }
     }
 }
@@ -1,5 +1,10 @@
 // Delete operation trait and implementation
 use crate::error::{DirectoryDeletionNotRecursiveSnafu, PartialDeletionSnafu, Result};
+use crate::storage::utils::dry_run::DryRun;
+use crate::storage::utils::filter::PathFilter;
+use crate::storage::utils::path::{basename, ensure_trailing_slash, get_root_relative_path};
+use crate::storage::utils::report::{Action, Failure, ItemStatus, PathRecord, Reporter};
+use futures::stream::TryStreamExt;
 use opendal::Operator;
 
 /// Trait for deleting files and directories from storage.
@@ -12,19 +17,73 @@ pub trait Deleter {
     /// * `recursive` - Whether to delete directories recursively
     ///
     /// # Returns
-    /// * `Result<()>` - Success or detailed error information
-    async fn delete(&self, paths: &[String], recursive: bool) -> Result<()>;
+    /// * `Result<Vec<PathRecord>>` - The outcome of every path or detailed error information
+    async fn delete(&self, paths: &[String], recursive: bool) -> Result<Vec<PathRecord>>;
 }
 
 /// Implementation of Deleter for OpenDAL Operator.
+///
+/// With a non-empty `filter`, only the selected files are deleted and directories
+/// themselves are kept. With a `dry_run` plan, deletions are recorded instead.
+/// The outcome of every path is passed to `reporter` as soon as it is known.
 pub struct OpenDalDeleter {
     operator: Operator,
+    filter: PathFilter,
+    dry_run: Option<DryRun>,
+    reporter: Reporter,
 }
 
 impl OpenDalDeleter {
-    /// Create a new deleter with the given OpenDAL operator.
-    pub fn new(operator: Operator) -> Self {
-        Self { operator }
+    /// Create a new deleter with the given OpenDAL operator, file filter, dry-run plan
+    /// and reporter.
+    pub fn new(
+        operator: Operator,
+        filter: PathFilter,
+        dry_run: Option<DryRun>,
+        reporter: Reporter,
+    ) -> Self {
+        Self {
+            operator,
+            filter,
+            dry_run,
+            reporter,
+        }
+    }
+
+    /// Report the outcome of a path and add it to `records`.
+    fn record(&self, records: &mut Vec<PathRecord>, record: PathRecord) {
+        self.reporter.path(&record);
+        records.push(record);
+    }
+
+    /// Delete a single path, recursively for directories, recording the outcome.
+    ///
+    /// # Returns
+    /// * `Option<Failure>` - The failure, if the deletion failed
+    async fn remove(
+        &self,
+        path: &str,
+        recursive: bool,
+        records: &mut Vec<PathRecord>,
+    ) -> Option<Failure> {
+        let result = if recursive {
+            self.operator.remove_all(path).await
+        } else {
+            self.operator.delete(path).await
+        };
+        match result {
+            Ok(_) => {
+                self.record(
+                    records,
+                    PathRecord::new(Action::Delete, ItemStatus::Ok, path),
+                );
+                None
+            }
+            Err(e) => {
+                self.record(records, PathRecord::failed(Action::Delete, path, &e));
+                Some(Failure::from_error(Action::Delete, path, &e.into()))
+            }
+        }
     }
 
     /// Check if a path exists in storage.
@@ -42,36 +101,112 @@ impl OpenDalDeleter {
             Err(_) => Ok(false),
         }
     }
+
+    /// Delete the files under `dir` that the filter selects, one by one.
+    ///
+    /// Every selected path is collected before the first deletion, so the listing is
+    /// never affected by the deletions themselves.
+    async fn delete_filtered(
+        &self,
+        dir: &str,
+        records: &mut Vec<PathRecord>,
+        failures: &mut Vec<Failure>,
+    ) -> Result<()> {
+        let mut selected = Vec::new();
+        let mut lister = self.operator.lister_with(dir).recursive(true).await?;
+        while let Some(entry) = lister.try_next().await? {
+            if !entry.metadata().mode().is_dir()
+                && self
+                    .filter
+                    .matches(&get_root_relative_path(entry.path(), dir))
+            {
+                selected.push(entry.path().to_string());
+            }
+        }
+
+        for path in selected {
+            if let Some(plan) = &self.dry_run {
+                self.record(records, plan.delete(&path));
+                continue;
+            }
+            failures.extend(self.remove(&path, false, records).await);
+        }
+        Ok(())
+    }
+
+    /// Record every path `remove_all` would delete under `path`, including itself.
+    async fn plan_remove_all(
+        &self,
+        plan: &DryRun,
+        path: &str,
+        is_dir: bool,
+        records: &mut Vec<PathRecord>,
+    ) -> Result<()> {
+        if !is_dir {
+            self.record(records, plan.delete(path));
+            return Ok(());
+        }
+        let root = ensure_trailing_slash(path);
+        let mut listed_root = false;
+        let mut lister = self.operator.lister_with(&root).recursive(true).await?;
+        while let Some(entry) = lister.try_next().await? {
+            listed_root |= entry.path() == root;
+            self.record(records, plan.delete(entry.path()));
+        }
+        if !listed_root {
+            self.record(records, plan.delete(&root));
+        }
+        Ok(())
+    }
 }
 
 impl Deleter for OpenDalDeleter {
-    async fn delete(&self, paths: &[String], recursive: bool) -> Result<()> {
-        let mut failed_paths = Vec::new();
+    async fn delete(&self, paths: &[String], recursive: bool) -> Result<Vec<PathRecord>> {
+        let mut records = Vec::new();
+        let mut failures = Vec::new();
 
         for path in paths {
             if !self.path_exists(path).await? {
-                eprintln!("Path not found: {path}");
-                failed_paths.push(path.clone());
+                let missing = PathRecord::new(Action::Delete, ItemStatus::Missing, path);
+                self.record(&mut records, missing);
+                failures.push(Failure::new(
+                    Action::Delete,
+                    path,
+                    "NotFound",
+                    "path does not exist",
+                ));
                 continue;
             }
 
-            if self.is_directory(path).await? && !recursive {
-                return DirectoryDeletionNotRecursiveSnafu { path: path.clone() }.fail();
+            let is_dir = self.is_directory(path).await?;
+            if is_dir && !recursive {
+                let result = DirectoryDeletionNotRecursiveSnafu { path: path.clone() }.fail();
+                return self.reporter.path_result(result, Action::Delete, path);
             }
 
-            match self.operator.remove_all(path).await {
-                Ok(_) => println!("Deleted: {path}"),
-                Err(e) => {
-                    eprintln!("Failed to delete {path}: {e}");
-                    failed_paths.push(path.clone());
+            if !self.filter.is_empty() {
+                if is_dir {
+                    self.delete_filtered(path, &mut records, &mut failures)
+                        .await?;
+                    continue;
+                }
+                if !self.filter.matches(&basename(path)) {
+                    continue;
                 }
             }
+
+            if let Some(plan) = &self.dry_run {
+                self.plan_remove_all(plan, path, is_dir, &mut records)
+                    .await?;
+                continue;
+            }
+            failures.extend(self.remove(path, true, &mut records).await);
         }
 
-        if !failed_paths.is_empty() {
-            return PartialDeletionSnafu { failed_paths }.fail();
+        if !failures.is_empty() {
+            return PartialDeletionSnafu { failures }.fail();
         }
 
-        Ok(())
+        Ok(records)
     }
 }
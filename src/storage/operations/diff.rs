@@ -0,0 +1,163 @@
+use crate::error::{InvalidPathSnafu, Result};
+use crate::storage::operations::stat::{ObjectMeta, OpenDalStater, Stater};
+use crate::storage::utils::filter::PathFilter;
+use crate::storage::utils::transfer::is_directory;
+use crate::storage::utils::walk::{ChangeReason, change_reason, collect_files, etags_comparable};
+use opendal::Operator;
+use serde::Serialize;
+use snafu::ensure;
+
+/// A file present in only one of the compared trees.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffEntry {
+    /// Path relative to the compared roots
+    pub path: String,
+    /// Size in bytes
+    pub size: u64,
+}
+
+/// A file present in both trees whose size, ETag or modification time differs.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModifiedEntry {
+    /// Path relative to the compared roots
+    pub path: String,
+    /// First difference found, in the order size, etag, mtime
+    pub reason: ChangeReason,
+    /// Size in the first tree
+    pub old_size: u64,
+    /// Size in the second tree
+    pub new_size: u64,
+}
+
+/// Counters and byte totals of a tree comparison.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub added_bytes: u64,
+    pub removed: usize,
+    pub removed_bytes: u64,
+    pub modified: usize,
+    pub unchanged: usize,
+}
+
+/// Differences between a first tree (A) and a second tree (B).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TreeDiff {
+    /// Files only in B
+    pub added: Vec<DiffEntry>,
+    /// Files only in A
+    pub removed: Vec<DiffEntry>,
+    /// Files in both trees that differ
+    pub modified: Vec<ModifiedEntry>,
+    /// Number of files in both trees that match
+    pub unchanged: usize,
+}
+
+impl TreeDiff {
+    /// Summarize the comparison for `--stat` output.
+    pub fn summary(&self) -> DiffSummary {
+        DiffSummary {
+            added: self.added.len(),
+            added_bytes: self.added.iter().map(|e| e.size).sum(),
+            removed: self.removed.len(),
+            removed_bytes: self.removed.iter().map(|e| e.size).sum(),
+            modified: self.modified.len(),
+            unchanged: self.unchanged,
+        }
+    }
+}
+
+/// Trait for comparing two storage trees.
+pub trait Differ {
+    /// Compare the files under `old_path` with those under `new_path`.
+    ///
+    /// # Arguments
+    /// * `old_path` - First directory (A)
+    /// * `new_path` - Second directory (B)
+    ///
+    /// # Returns
+    /// * `Result<TreeDiff>` - Added, removed and modified files
+    async fn diff(&self, old_path: &str, new_path: &str) -> Result<TreeDiff>;
+}
+
+/// Implementation of Differ for OpenDAL Operators.
+///
+/// Either side may be a remote bucket or the local filesystem (via an `fs` operator).
+/// Files are compared the same way `sync` decides what to transfer from A to B.
+pub struct OpenDalDiffer {
+    old: Operator,
+    new: Operator,
+    filter: PathFilter,
+}
+
+impl OpenDalDiffer {
+    /// Create a new differ comparing the files `filter` selects in trees of `old` (A)
+    /// with those in trees of `new` (B).
+    pub fn new(old: Operator, new: Operator, filter: PathFilter) -> Self {
+        Self { old, new, filter }
+    }
+
+    /// Fill in an ETag the listing did not report, so equal-sized files can still be
+    /// told apart by content.
+    async fn with_etag(operator: &Operator, meta: &ObjectMeta) -> Result<ObjectMeta> {
+        if meta.etag.is_some() {
+            return Ok(meta.clone());
+        }
+        OpenDalStater::new(operator.clone()).stat(&meta.path).await
+    }
+}
+
+impl Differ for OpenDalDiffer {
+    async fn diff(&self, old_path: &str, new_path: &str) -> Result<TreeDiff> {
+        for (operator, path) in [(&self.old, old_path), (&self.new, new_path)] {
+            ensure!(
+                is_directory(operator, path).await,
+                InvalidPathSnafu {
+                    path: path.to_string()
+                }
+            );
+        }
+
+        let old_files = collect_files(&self.old, old_path, &self.filter).await?;
+        let new_files = collect_files(&self.new, new_path, &self.filter).await?;
+        let compare_etag = etags_comparable(&self.old, &self.new);
+        let mut diff = TreeDiff::default();
+
+        for (relative, old_meta) in &old_files {
+            let Some(new_meta) = new_files.get(relative) else {
+                diff.removed.push(DiffEntry {
+                    path: relative.clone(),
+                    size: old_meta.size,
+                });
+                continue;
+            };
+            let reason = if compare_etag && old_meta.size == new_meta.size {
+                let old_meta = Self::with_etag(&self.old, old_meta).await?;
+                let new_meta = Self::with_etag(&self.new, new_meta).await?;
+                change_reason(&old_meta, &new_meta, compare_etag, true)
+            } else {
+                change_reason(old_meta, new_meta, compare_etag, true)
+            };
+            match reason {
+                Some(reason) => diff.modified.push(ModifiedEntry {
+                    path: relative.clone(),
+                    reason,
+                    old_size: old_meta.size,
+                    new_size: new_meta.size,
+                }),
+                None => diff.unchanged += 1,
+            }
+        }
+
+        for (relative, new_meta) in &new_files {
+            if !old_files.contains_key(relative) {
+                diff.added.push(DiffEntry {
+                    path: relative.clone(),
+                    size: new_meta.size,
+                });
+            }
+        }
+
+        Ok(diff)
+    }
+}
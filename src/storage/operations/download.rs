@@ -1,9 +1,17 @@
-use crate::error::{Error, Result};
+use crate::error::{Error, IncompleteTransferSnafu, Result};
+use crate::storage::utils::checksum::{TransferHasher, verify_object};
+use crate::storage::utils::options::TransferOptions;
 use crate::storage::utils::path::get_root_relative_path;
+use crate::storage::utils::report::{Action, TransferMethod, TransferRecord, TransferReport};
+use crate::storage::utils::resume::{self, ResumeState};
+use crate::storage::utils::throttle::throttle;
+use crate::storage::utils::transfer::{TransferTask, run_transfers};
 use futures::stream::TryStreamExt;
-use opendal::{EntryMode, Operator};
+use opendal::{EntryMode, Metadata, Operator};
+use snafu::ensure;
 use std::path::{Path, PathBuf};
 use tokio::fs;
+use tokio::io::AsyncWriteExt;
 
 /// Trait for downloading files and directories from storage.
 pub trait Downloader {
@@ -14,24 +22,199 @@ pub trait Downloader {
     /// * `local_path` - Destination path on local filesystem
     ///
     /// # Returns
-    /// * `Result<()>` - Success or detailed error information
-    async fn download(&self, remote_path: &str, local_path: &str) -> Result<()>;
+    /// * `Result<TransferReport>` - The downloaded files or detailed error information
+    async fn download(&self, remote_path: &str, local_path: &str) -> Result<TransferReport>;
 }
 
 /// Implementation of Downloader for OpenDAL Operator.
 pub struct OpenDalDownloader {
     operator: Operator,
+    options: TransferOptions,
 }
 
 impl OpenDalDownloader {
-    /// Create a new downloader with the given OpenDAL operator.
-    pub fn new(operator: Operator) -> Self {
-        Self { operator }
+    /// Create a new downloader with the given OpenDAL operator and transfer options.
+    pub fn new(operator: Operator, options: TransferOptions) -> Self {
+        Self { operator, options }
+    }
+
+    /// Stream a single remote file to a local path, reporting its progress to `options.progress`.
+    ///
+    /// The object is fetched as ranges of `part_size_for(size)` bytes, up to `concurrency`
+    /// at a time, and written to disk in order, so memory use is bounded by
+    /// `part_size * concurrency` regardless of the object size.
+    ///
+    /// Data goes to a temporary sibling that is renamed into place only once the full
+    /// content has been written and verified, so an interrupted download never leaves a
+    /// truncated file at `local_file_path`. With `verify`, the digests computed while
+    /// streaming must also match the checksum the remote object advertises (or a
+    /// recomputed MD5).
+    ///
+    /// On a dry run the download is only recorded.
+    async fn download_file(
+        &self,
+        remote_file_path: &str,
+        local_file_path: &Path,
+    ) -> Result<TransferRecord> {
+        let meta = match self.operator.stat(remote_file_path).await {
+            Ok(meta) => meta,
+            // Gracefully skip objects that cannot be found due to key normalization issues
+            Err(e) if e.kind() == opendal::ErrorKind::NotFound => {
+                log::warn!(
+                    "Skip not found at read (likely normalized key): {}",
+                    remote_file_path
+                );
+                let dest = local_file_path.to_string_lossy();
+                return Ok(TransferRecord::skipped(
+                    Action::Download,
+                    remote_file_path,
+                    &dest,
+                ));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let file_size = meta.content_length();
+        if let Some(plan) = &self.options.dry_run {
+            let dest = local_file_path.to_string_lossy();
+            return Ok(plan.transfer(Action::Download, remote_file_path, &dest, file_size));
+        }
+        let partial_path = resume::partial_path(local_file_path);
+
+        // The state file lets a later `--resume` run continue an interrupted download.
+        let state = ResumeState::from_remote(remote_file_path, &meta);
+        let offset = if self.options.resume {
+            let offset = self
+                .resume_offset(local_file_path, &partial_path, &state)
+                .await;
+            resume::save(local_file_path, &state).await?;
+            offset
+        } else {
+            0
+        };
+
+        let part_size = self.options.part_size_for(file_size);
+        let mut stream = self
+            .operator
+            .reader_with(remote_file_path)
+            .chunk(part_size as usize)
+            .concurrent(self.options.concurrency)
+            .await?
+            .into_bytes_stream(offset..file_size)
+            .await?;
+
+        let file_progress =
+            self.options
+                .progress
+                .start_file(Action::Download, remote_file_path, file_size);
+
+        let mut file = if offset > 0 {
+            self.options.progress.file_resumed(remote_file_path, offset);
+            fs::OpenOptions::new()
+                .append(true)
+                .open(&partial_path)
+                .await?
+        } else {
+            fs::File::create(&partial_path).await?
+        };
+        let mut hasher = None;
+        if self.options.verify {
+            let mut digests = TransferHasher::default();
+            digests.update_from_file(&partial_path, offset).await?;
+            hasher = Some(digests);
+        }
+
+        let mut total_bytes = offset;
+        while let Some(bytes) = stream.try_next().await? {
+            if let Some(hasher) = &mut hasher {
+                hasher.update(&bytes);
+            }
+            file.write_all(&bytes).await?;
+            throttle(self.options.rate_limit.as_ref(), bytes.len() as u64).await;
+            total_bytes += bytes.len() as u64;
+            file_progress.set(total_bytes);
+        }
+        file.flush().await?;
+        file.sync_all().await?;
+        drop(file);
+        file_progress.finish(total_bytes);
+
+        if let Err(e) = self
+            .check_download(remote_file_path, &meta, total_bytes, hasher)
+            .await
+        {
+            let _ = fs::remove_file(&partial_path).await;
+            resume::clear(local_file_path).await;
+            return Err(e);
+        }
+        fs::rename(&partial_path, local_file_path).await?;
+        resume::clear(local_file_path).await;
+
+        Ok(TransferRecord::done(
+            Action::Download,
+            remote_file_path,
+            &local_file_path.to_string_lossy(),
+            Some(total_bytes),
+            TransferMethod::Stream,
+        ))
+    }
+
+    /// Check a finished download against the remote object before it is put in place.
+    async fn check_download(
+        &self,
+        remote_file_path: &str,
+        meta: &Metadata,
+        total_bytes: u64,
+        hasher: Option<TransferHasher>,
+    ) -> Result<()> {
+        ensure!(
+            total_bytes == meta.content_length(),
+            IncompleteTransferSnafu {
+                path: remote_file_path.to_string(),
+                expected: meta.content_length(),
+                actual: total_bytes,
+            }
+        );
+        if let Some(hasher) = hasher {
+            verify_object(&self.operator, remote_file_path, &hasher.finish()).await?;
+        }
+        Ok(())
+    }
+
+    /// Number of bytes of a partial download that can be kept when resuming.
+    ///
+    /// The partial file is only trusted when the state recorded by the interrupted run
+    /// still matches the remote object; otherwise the download restarts from zero.
+    async fn resume_offset(
+        &self,
+        local_file_path: &Path,
+        partial_path: &Path,
+        state: &ResumeState,
+    ) -> u64 {
+        let Ok(partial) = fs::metadata(partial_path).await else {
+            return 0;
+        };
+        match resume::load(local_file_path).await {
+            Some(recorded) if recorded == *state && partial.len() <= state.size => partial.len(),
+            Some(_) => {
+                self.options.progress.warning(&format!(
+                    "{} changed since the interrupted download; restarting",
+                    state.remote_path
+                ));
+                0
+            }
+            None => {
+                self.options.progress.warning(&format!(
+                    "no resume state for {}; restarting",
+                    local_file_path.display()
+                ));
+                0
+            }
+        }
     }
 }
 
 impl Downloader for OpenDalDownloader {
-    async fn download(&self, remote_path: &str, local_path: &str) -> Result<()> {
+    async fn download(&self, remote_path: &str, local_path: &str) -> Result<TransferReport> {
         if !self.operator.exists(remote_path).await? {
             return Err(Error::PathNotFound {
                 path: PathBuf::from(remote_path),
@@ -44,6 +227,7 @@ impl Downloader for OpenDalDownloader {
             .recursive(true)
             .await?;
 
+        let mut tasks = Vec::new();
         let mut stream = lister;
         while let Some(entry) = stream.try_next().await? {
             let meta = entry.metadata();
@@ -64,37 +248,30 @@ impl Downloader for OpenDalDownloader {
                     .map(|s| s.to_string_lossy().to_string())
                     .unwrap_or_default();
             }
-            let local_file_path = Path::new(local_path).join(relative_path);
+            let local_file_path = Path::new(local_path).join(&relative_path);
 
+            let dry_run = self.options.dry_run.is_some();
             if meta.mode() == EntryMode::DIR {
-                fs::create_dir_all(&local_file_path).await?;
-            } else {
-                if let Some(parent) = local_file_path.parent() {
-                    fs::create_dir_all(parent).await?;
+                if !dry_run {
+                    fs::create_dir_all(&local_file_path).await?;
                 }
-                match self.operator.read(remote_file_path).await {
-                    Ok(data) => {
-                        fs::write(&local_file_path, data.to_vec()).await?;
-                        println!(
-                            "Downloaded: {remote_file_path} → {}",
-                            local_file_path.display()
-                        );
-                    }
-                    Err(e) => {
-                        // Gracefully skip objects that cannot be found due to key normalization issues
-                        if e.kind() == opendal::ErrorKind::NotFound {
-                            log::warn!(
-                                "Skip not found at read (likely normalized key): {}",
-                                remote_file_path
-                            );
-                            continue;
-                        }
-                        return Err(e.into());
-                    }
+            } else if self.options.filter.matches(&relative_path) {
+                if let Some(parent) = local_file_path.parent()
+                    && !dry_run
+                {
+                    fs::create_dir_all(parent).await?;
                 }
+                tasks.push(TransferTask::new(
+                    remote_file_path,
+                    local_file_path.to_string_lossy(),
+                    meta.content_length(),
+                ));
             }
         }
 
-        Ok(())
+        run_transfers(tasks, Action::Download, &self.options, |task| async move {
+            self.download_file(&task.src, Path::new(&task.dest)).await
+        })
+        .await
     }
 }
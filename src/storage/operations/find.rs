@@ -0,0 +1,251 @@
+use crate::error::{PartialDeletionSnafu, Result};
+use crate::storage::utils::dry_run::DryRun;
+use crate::storage::utils::path::ensure_trailing_slash;
+use crate::storage::utils::report::{Action, Failure, ItemStatus, PathRecord, Reporter};
+use chrono::{DateTime, Utc};
+use futures::stream::TryStreamExt;
+use globset::GlobMatcher;
+use opendal::{EntryMode, Metadata, Operator};
+use std::collections::HashSet;
+
+/// Numeric test in `find` style: `+N` (more than N), `-N` (less than N) or `N` (exactly N).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    MoreThan(u64),
+    LessThan(u64),
+    Exactly(u64),
+}
+
+impl Comparison {
+    /// Parse `+N`, `-N` or `N`, reading `N` with `parse_number`.
+    pub fn parse(value: &str, parse_number: impl Fn(&str) -> Option<u64>) -> Option<Self> {
+        let value = value.trim();
+        if let Some(number) = value.strip_prefix('+') {
+            parse_number(number).map(Self::MoreThan)
+        } else if let Some(number) = value.strip_prefix('-') {
+            parse_number(number).map(Self::LessThan)
+        } else {
+            parse_number(value).map(Self::Exactly)
+        }
+    }
+
+    fn matches(self, actual: u64) -> bool {
+        match self {
+            Self::MoreThan(n) => actual > n,
+            Self::LessThan(n) => actual < n,
+            Self::Exactly(n) => actual == n,
+        }
+    }
+}
+
+/// Entry type selected by `-type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Dir,
+}
+
+/// Predicates an entry must all satisfy to be found.
+#[derive(Debug, Clone, Default)]
+pub struct FindPredicates {
+    /// Glob the entry name must match
+    pub name: Option<GlobMatcher>,
+    /// Glob the entry name must match, ignoring case
+    pub iname: Option<GlobMatcher>,
+    /// Size in bytes
+    pub size: Option<Comparison>,
+    /// Whole days since the last modification
+    pub mtime: Option<Comparison>,
+    /// Entry type
+    pub kind: Option<EntryKind>,
+}
+
+impl FindPredicates {
+    /// Whether size or modification time is needed to evaluate the predicates.
+    fn needs_metadata(&self) -> bool {
+        self.size.is_some() || self.mtime.is_some()
+    }
+
+    fn matches(&self, path: &str, meta: &Metadata, now: DateTime<Utc>) -> bool {
+        let is_dir = meta.mode().is_dir();
+        match self.kind {
+            Some(EntryKind::File) if is_dir => return false,
+            Some(EntryKind::Dir) if !is_dir => return false,
+            _ => {}
+        }
+
+        let name = path
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or(path);
+        if self.name.as_ref().is_some_and(|glob| !glob.is_match(name))
+            || self.iname.as_ref().is_some_and(|glob| !glob.is_match(name))
+        {
+            return false;
+        }
+
+        if let Some(size) = self.size
+            && !size.matches(meta.content_length())
+        {
+            return false;
+        }
+
+        if let Some(mtime) = self.mtime {
+            let Some(modified) = meta.last_modified() else {
+                return false;
+            };
+            let days = (now - modified).num_seconds().max(0) as u64 / 86_400;
+            if !mtime.matches(days) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Trait for finding entries by name, size, age and type.
+pub trait Finder {
+    /// Recursively find the entries under `path` that satisfy every predicate.
+    ///
+    /// # Arguments
+    /// * `path` - Directory to search (or a single file to test)
+    /// * `predicates` - Tests an entry must pass
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - Paths of the matching entries, in listing order
+    async fn find(&self, path: &str, predicates: &FindPredicates) -> Result<Vec<String>>;
+
+    /// Delete entries returned by `find`.
+    ///
+    /// Entries are removed deepest first. As with `find -delete`, directories are only
+    /// removed when they are empty; non-empty ones are reported and kept.
+    ///
+    /// # Arguments
+    /// * `paths` - Paths of the entries to delete
+    ///
+    /// # Returns
+    /// * `Result<Vec<PathRecord>>` - The outcome of every entry or detailed error information
+    async fn delete_found(&self, paths: &[String]) -> Result<Vec<PathRecord>>;
+}
+
+/// Implementation of Finder for OpenDAL Operator.
+///
+/// With a `dry_run` plan, `delete_found` records deletions instead of issuing them.
+/// The outcome of every deletion is passed to `reporter` as soon as it is known.
+pub struct OpenDalFinder {
+    operator: Operator,
+    dry_run: Option<DryRun>,
+    reporter: Reporter,
+}
+
+impl OpenDalFinder {
+    /// Create a new finder with the given OpenDAL operator, dry-run plan and reporter.
+    pub fn new(operator: Operator, dry_run: Option<DryRun>, reporter: Reporter) -> Self {
+        Self {
+            operator,
+            dry_run,
+            reporter,
+        }
+    }
+
+    /// Whether a directory has no entries besides itself and those already `deleted`.
+    ///
+    /// `deleted` matters on dry runs, where earlier deletions were not actually issued.
+    async fn is_empty_dir(&self, path: &str, deleted: &HashSet<String>) -> Result<bool> {
+        let entries = self.operator.list(path).await?;
+        Ok(entries
+            .iter()
+            .all(|entry| entry.path() == path || deleted.contains(entry.path())))
+    }
+}
+
+impl Finder for OpenDalFinder {
+    async fn find(&self, path: &str, predicates: &FindPredicates) -> Result<Vec<String>> {
+        let root = ensure_trailing_slash(path.trim_start_matches('/'));
+        let now = Utc::now();
+        let implied_dir = Metadata::new(EntryMode::DIR);
+        let mut seen_dirs = HashSet::new();
+        let mut found = Vec::new();
+
+        let mut lister = self.operator.lister_with(path).recursive(true).await?;
+        while let Some(entry) = lister.try_next().await? {
+            let entry_path = entry.path();
+            let relative = entry_path.trim_start_matches('/');
+            if relative == root {
+                continue;
+            }
+
+            // Object stores list recursively without directory entries, so directories
+            // implied by a key are reported (once) before their first entry.
+            if let Some(parents) = relative.strip_prefix(root.as_str()) {
+                for (end, _) in parents.trim_end_matches('/').match_indices('/') {
+                    let dir = &entry_path[..entry_path.len() - parents.len() + end + 1];
+                    if seen_dirs.insert(dir.to_string())
+                        && predicates.matches(dir, &implied_dir, now)
+                    {
+                        found.push(dir.to_string());
+                    }
+                }
+            }
+            if entry.metadata().mode().is_dir() && !seen_dirs.insert(entry_path.to_string()) {
+                continue;
+            }
+
+            let listed = entry.metadata();
+            let stated;
+            let meta = if predicates.needs_metadata()
+                && !listed.mode().is_dir()
+                && listed.last_modified().is_none()
+            {
+                // Some backends (such as `fs`) list entries without sizes or timestamps
+                stated = self.operator.stat(entry_path).await?;
+                &stated
+            } else {
+                listed
+            };
+
+            if predicates.matches(entry_path, meta, now) {
+                found.push(entry_path.to_string());
+            }
+        }
+
+        Ok(found)
+    }
+
+    async fn delete_found(&self, paths: &[String]) -> Result<Vec<PathRecord>> {
+        let mut ordered = paths.to_vec();
+        ordered.sort_by(|a, b| b.cmp(a));
+        let mut records = Vec::new();
+        let mut failures = Vec::new();
+        let mut deleted = HashSet::new();
+
+        for path in ordered {
+            let record = if path.ends_with('/') && !self.is_empty_dir(&path, &deleted).await? {
+                PathRecord::new(Action::Delete, ItemStatus::Skipped, &path)
+            } else if let Some(plan) = &self.dry_run {
+                deleted.insert(path.clone());
+                plan.delete(&path)
+            } else {
+                match self.operator.delete(&path).await {
+                    Ok(_) => {
+                        deleted.insert(path.clone());
+                        PathRecord::new(Action::Delete, ItemStatus::Ok, &path)
+                    }
+                    Err(e) => {
+                        let record = PathRecord::failed(Action::Delete, &path, &e);
+                        failures.push(Failure::from_error(Action::Delete, &path, &e.into()));
+                        record
+                    }
+                }
+            };
+            self.reporter.path(&record);
+            records.push(record);
+        }
+
+        if !failures.is_empty() {
+            return PartialDeletionSnafu { failures }.fail();
+        }
+        Ok(records)
+    }
+}
@@ -1,5 +1,7 @@
 // Directory creation operation trait and implementation
 use crate::error::{Error, Result};
+use crate::storage::utils::dry_run::DryRun;
+use crate::storage::utils::report::{Action, ItemStatus, PathRecord, Reporter};
 use opendal::Operator;
 
 /// Trait for creating directories in storage.
@@ -12,19 +14,34 @@ pub trait Mkdirer {
     /// * `parents` - Whether to create parent directories as needed
     ///
     /// # Returns
-    /// * `Result<()>` - Success or detailed error information
-    async fn mkdir(&self, path: &str, parents: bool) -> Result<()>;
+    /// * `Result<Vec<PathRecord>>` - The outcome of every directory or detailed error information
+    async fn mkdir(&self, path: &str, parents: bool) -> Result<Vec<PathRecord>>;
 }
 
 /// Implementation of Mkdirer for OpenDAL Operator.
+///
+/// With a `dry_run` plan, directories are recorded instead of created. The outcome of
+/// every directory is passed to `reporter` as soon as it is known.
 pub struct OpenDalMkdirer {
     operator: Operator,
+    dry_run: Option<DryRun>,
+    reporter: Reporter,
 }
 
 impl OpenDalMkdirer {
-    /// Create a new mkdirer with the given OpenDAL operator.
-    pub fn new(operator: Operator) -> Self {
-        Self { operator }
+    /// Create a new mkdirer with the given OpenDAL operator, dry-run plan and reporter.
+    pub fn new(operator: Operator, dry_run: Option<DryRun>, reporter: Reporter) -> Self {
+        Self {
+            operator,
+            dry_run,
+            reporter,
+        }
+    }
+
+    /// Report the outcome of a directory and add it to `records`.
+    fn record(&self, records: &mut Vec<PathRecord>, record: PathRecord) {
+        self.reporter.path(&record);
+        records.push(record);
     }
 
     /// Normalize directory path by ensuring it ends with a slash.
@@ -58,15 +75,29 @@ impl OpenDalMkdirer {
     }
 
     /// Create a single directory.
-    async fn create_single_directory(&self, path: &str) -> Result<()> {
+    async fn create_single_directory(
+        &self,
+        path: &str,
+        records: &mut Vec<PathRecord>,
+    ) -> Result<()> {
         if path.is_empty() {
-            println!("Note: Root directory '/' already exists (bucket root)");
+            self.record(
+                records,
+                PathRecord::new(Action::Mkdir, ItemStatus::Exists, "/"),
+            );
+            return Ok(());
+        }
+        if let Some(plan) = &self.dry_run {
+            self.record(records, plan.mkdir(path));
             return Ok(());
         }
 
         match self.operator.create_dir(path).await {
             Ok(_) => {
-                println!("Created directory: {}", path);
+                self.record(
+                    records,
+                    PathRecord::new(Action::Mkdir, ItemStatus::Ok, path),
+                );
                 Ok(())
             }
             Err(e) => {
@@ -74,7 +105,8 @@ impl OpenDalMkdirer {
                 if e.to_string().contains("already exists")
                     || e.to_string().contains("BucketAlreadyOwnedByYou")
                 {
-                    println!("Directory already exists: {}", path);
+                    let exists = PathRecord::new(Action::Mkdir, ItemStatus::Exists, path);
+                    self.record(records, exists);
                     Ok(())
                 } else {
                     Err(Error::DirectoryCreationFailed {
@@ -87,20 +119,24 @@ impl OpenDalMkdirer {
     }
 
     /// Create parent directories recursively.
-    async fn create_parent_directories(&self, path: &str) -> Result<()> {
+    async fn create_parent_directories(
+        &self,
+        path: &str,
+        records: &mut Vec<PathRecord>,
+    ) -> Result<()> {
         let components: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
 
         // Create all parent directories
         for i in 1..=components.len() {
             let current_path = components[..i].join("/") + "/";
             if !self.directory_exists(&current_path).await? {
-                self.create_single_directory(&current_path).await?;
+                self.create_single_directory(&current_path, records).await?;
             }
         }
 
         // Also create the final directory if it's not empty
         if !path.is_empty() {
-            self.create_single_directory(path).await?;
+            self.create_single_directory(path, records).await?;
         }
 
         Ok(())
@@ -108,13 +144,17 @@ impl OpenDalMkdirer {
 }
 
 impl Mkdirer for OpenDalMkdirer {
-    async fn mkdir(&self, path: &str, parents: bool) -> Result<()> {
+    async fn mkdir(&self, path: &str, parents: bool) -> Result<Vec<PathRecord>> {
         let normalized_path = self.normalize_directory_path(path);
+        let mut records = Vec::new();
 
         if parents {
-            self.create_parent_directories(&normalized_path).await
+            self.create_parent_directories(&normalized_path, &mut records)
+                .await?;
         } else {
-            self.create_single_directory(&normalized_path).await
+            self.create_single_directory(&normalized_path, &mut records)
+                .await?;
         }
+        Ok(records)
     }
 }
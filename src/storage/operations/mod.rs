@@ -1,23 +1,33 @@
 // Storage operation traits and implementations
 pub mod cat;
+pub mod checksum;
 pub mod copy;
 pub mod delete;
+pub mod diff;
 pub mod download;
+pub mod find;
 pub mod list;
 pub mod mkdir;
 pub mod mv;
 pub mod stat;
+pub mod sync;
 pub mod upload;
 pub mod usage;
+pub mod verify;
 
 // Re-export all operation traits - all are now implemented
 pub use cat::Cater;
+pub use checksum::Checksummer;
 pub use copy::Copier;
 pub use delete::Deleter;
+pub use diff::Differ;
 pub use download::Downloader;
+pub use find::Finder;
 pub use list::Lister;
 pub use mkdir::Mkdirer;
 pub use mv::Mover;
 pub use stat::Stater;
+pub use sync::Syncer;
 pub use upload::Uploader;
 pub use usage::UsageCalculator;
+pub use verify::Verifier;
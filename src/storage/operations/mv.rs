@@ -1,15 +1,17 @@
 use crate::error::{InvalidPathSnafu, Result};
-use crate::storage::constants::DEFAULT_CHUNK_SIZE;
-use crate::storage::utils::path::{
-    basename, build_remote_path, ensure_trailing_slash, get_root_relative_path,
+use crate::storage::utils::backend::Backend;
+use crate::storage::utils::options::TransferOptions;
+use crate::storage::utils::path::{basename, build_remote_path, get_root_relative_path};
+use crate::storage::utils::report::{Action, TransferMethod, TransferRecord, TransferReport};
+use crate::storage::utils::transfer::{
+    TransferTask, ensure_directory, is_directory, run_transfer, run_transfers, same_storage,
+    server_side_copy, stream_copy,
 };
-use crate::storage::utils::progress::ConsoleProgressReporter;
-use async_recursion::async_recursion;
 use futures::stream::TryStreamExt;
-use opendal::{EntryMode, Operator};
+use opendal::EntryMode;
 use snafu::ensure;
 
-/// Trait for moving files and directories within storage.
+/// Trait for moving files and directories between storage locations.
 pub trait Mover {
     /// Change the name of a single file or entire directory.
     /// Move a single file or entire directory from one location to another in object storage.
@@ -19,49 +21,36 @@ pub trait Mover {
     /// * `dest_path` - Destination path in object storage
     ///
     /// # Returns
-    /// * `Result<()>` - Success or detailed error information
-    async fn mover(&self, src_path: &str, dest_path: &str) -> Result<()>;
+    /// * `Result<TransferReport>` - The moved files or detailed error information
+    async fn mover(&self, src_path: &str, dest_path: &str) -> Result<TransferReport>;
 }
 
-/// Implementation of Mover for OpenDAL Operator.
+/// Implementation of Mover for OpenDAL Operators.
+///
+/// Source and destination operators may point at different providers or buckets;
+/// each file is streamed to the destination and then deleted from the source. Within a
+/// single storage, files are renamed or copied server-side when the backend supports it.
 pub struct OpenDalMover {
-    operator: Operator,
+    src: Backend,
+    dest: Backend,
+    options: TransferOptions,
 }
 
 impl OpenDalMover {
-    /// Create a new copier with the given OpenDAL operator.
-    pub fn new(operator: Operator) -> Self {
-        Self { operator }
-    }
-
-    /// Hybrid directory detection for object storage: stat first; if not available, probe prefix.
-    async fn is_directory(&self, path: &str) -> bool {
-        match self.operator.stat(path).await.ok().map(|m| m.mode()) {
-            Some(EntryMode::DIR) => true,
-            Some(_) => false,
-            None => {
-                let probe = ensure_trailing_slash(path);
-                self.operator
-                    .list_with(&probe)
-                    .limit(1)
-                    .await
-                    .map(|entries| !entries.is_empty())
-                    .unwrap_or(false)
-            }
-        }
-    }
-
-    /// Ensure a remote directory exists (appends trailing '/').
-    async fn ensure_directory(&self, dir_path: &str) -> Result<()> {
-        let to_create = ensure_trailing_slash(dir_path);
-        self.operator.create_dir(&to_create).await?;
-        Ok(())
+    /// Create a new mover reading from `src` and writing to `dest`.
+    pub fn new(src: Backend, dest: Backend, options: TransferOptions) -> Self {
+        Self { src, dest, options }
     }
 
     /// Move files recursively with directory structure preservation.
-    #[async_recursion]
-    async fn move_file_recursive(&self, src_path: &str, dest_path: &str) -> Result<()> {
-        let lister = self.operator.lister_with(src_path).recursive(true).await?;
+    async fn move_file_recursive(&self, src_path: &str, dest_path: &str) -> Result<TransferReport> {
+        let lister = self
+            .src
+            .operator
+            .lister_with(src_path)
+            .recursive(true)
+            .await?;
+        let mut tasks = Vec::new();
 
         let mut stream = lister;
         while let Some(entry) = stream.try_next().await? {
@@ -79,66 +68,90 @@ impl OpenDalMover {
             let new_dest_path = build_remote_path(dest_path, &relative_path);
 
             if meta.mode() == EntryMode::DIR {
-                self.ensure_directory(&new_dest_path).await?;
-            } else {
-                self.stream_move(entry_path, &new_dest_path).await?;
-                self.operator.delete(entry_path).await?;
+                ensure_directory(&self.dest.operator, &new_dest_path, &self.options).await?;
+            } else if self.options.filter.matches(&relative_path) {
+                tasks.push(TransferTask::new(
+                    entry_path,
+                    new_dest_path,
+                    meta.content_length(),
+                ));
             }
         }
 
-        Ok(())
+        run_transfers(tasks, Action::Move, &self.options, |task| async move {
+            self.move_single(&task.src, &task.dest).await
+        })
+        .await
     }
 
-    /// Stream move a single file with progress reporting.
-    async fn stream_move(&self, src_path: &str, dest_path: &str) -> opendal::Result<()> {
-        let metadata = self.operator.stat(src_path).await?;
-        let file_size = metadata.content_length();
-
-        let mut writer = self.operator.writer(dest_path).await?;
-        let mut total_bytes = 0u64;
-        let mut offset = 0u64;
-
-        let reporter = ConsoleProgressReporter::new(
-            format!("Moving {src_path}"),
-            Some(file_size),
-            DEFAULT_CHUNK_SIZE as u64,
-        );
-
-        loop {
-            if offset >= file_size {
-                break;
+    /// Move a single file: rename it server-side when possible, otherwise copy it
+    /// (server-side or streamed) and then delete the source.
+    ///
+    /// The source is only deleted once the copy succeeded and, with `verify`, its
+    /// checksum matched. Renames are skipped under `verify`, since they remove the
+    /// source before the result could be checked. On a dry run the move is only recorded.
+    async fn move_single(&self, src_path: &str, dest_path: &str) -> Result<TransferRecord> {
+        if let Some(plan) = &self.options.dry_run {
+            let size = self.src.operator.stat(src_path).await?.content_length();
+            return Ok(plan.transfer(Action::Move, src_path, dest_path, size));
+        }
+        if self.options.server_side && same_storage(&self.src, &self.dest) {
+            let capability = self.src.operator.info().full_capability();
+            if capability.rename && !self.options.verify {
+                self.src.server_side.rename(src_path, dest_path).await?;
+                return Ok(moved(
+                    src_path,
+                    dest_path,
+                    None,
+                    TransferMethod::ServerSideRename,
+                ));
             }
-
-            let chunk_size = std::cmp::min(DEFAULT_CHUNK_SIZE as u64, file_size - offset);
-
-            let data = self
-                .operator
-                .read_with(src_path)
-                .range(offset..offset + chunk_size)
-                .await?;
-            let data_len = data.len();
-            if data_len == 0 {
-                break;
+            if capability.copy
+                && server_side_copy(&self.src, src_path, dest_path, self.options.verify).await?
+            {
+                self.src.operator.delete(src_path).await?;
+                return Ok(moved(
+                    src_path,
+                    dest_path,
+                    None,
+                    TransferMethod::ServerSideCopy,
+                ));
             }
-
-            writer.write(data).await?;
-            total_bytes += data_len as u64;
-            offset += data_len as u64;
-
-            reporter.maybe_report(total_bytes);
         }
 
-        writer.close().await?;
-        println!("\n✅ Moved: {src_path} → {dest_path} ({total_bytes} bytes)");
-
-        Ok(())
+        let total_bytes = stream_copy(
+            &self.src,
+            src_path,
+            &self.dest,
+            dest_path,
+            Action::Move,
+            &self.options,
+            self.options.verify,
+        )
+        .await?;
+        self.src.operator.delete(src_path).await?;
+        Ok(moved(
+            src_path,
+            dest_path,
+            Some(total_bytes),
+            TransferMethod::Stream,
+        ))
     }
 }
 
+fn moved(
+    src_path: &str,
+    dest_path: &str,
+    bytes: Option<u64>,
+    method: TransferMethod,
+) -> TransferRecord {
+    TransferRecord::done(Action::Move, src_path, dest_path, bytes, method)
+}
+
 impl Mover for OpenDalMover {
-    async fn mover(&self, src_path: &str, dest_path: &str) -> Result<()> {
-        let src_stat = self.operator.stat(src_path).await.ok();
-        let src_is_dir = self.is_directory(src_path).await;
+    async fn mover(&self, src_path: &str, dest_path: &str) -> Result<TransferReport> {
+        let src_stat = self.src.operator.stat(src_path).await.ok();
+        let src_is_dir = is_directory(&self.src.operator, src_path).await;
         ensure!(
             src_stat.is_some() || src_is_dir,
             InvalidPathSnafu {
@@ -147,24 +160,23 @@ impl Mover for OpenDalMover {
         );
 
         if src_is_dir {
-            let target_root = if self.is_directory(dest_path).await {
+            let target_root = if is_directory(&self.dest.operator, dest_path).await {
                 let base_name = basename(src_path);
                 let target_root = build_remote_path(dest_path, &base_name);
-                self.ensure_directory(&target_root).await?;
+                ensure_directory(&self.dest.operator, &target_root, &self.options).await?;
                 target_root
             } else {
-                self.ensure_directory(dest_path).await?;
+                ensure_directory(&self.dest.operator, dest_path, &self.options).await?;
                 dest_path.to_string()
             };
 
-            self.move_file_recursive(src_path, &target_root).await?;
-            Ok(())
+            self.move_file_recursive(src_path, &target_root).await
         } else {
             let dest_is_dir_hint = dest_path.ends_with('/');
-            let mut dest_is_dir = self.is_directory(dest_path).await;
+            let mut dest_is_dir = is_directory(&self.dest.operator, dest_path).await;
 
             if dest_is_dir_hint && !dest_is_dir {
-                self.operator.create_dir(dest_path).await?;
+                ensure_directory(&self.dest.operator, dest_path, &self.options).await?;
                 dest_is_dir = true;
             }
 
@@ -175,9 +187,8 @@ impl Mover for OpenDalMover {
                 dest_path.to_string()
             };
 
-            self.stream_move(src_path, &final_dest).await?;
-            self.operator.delete(src_path).await?;
-            Ok(())
+            let transfer = self.move_single(src_path, &final_dest);
+            run_transfer(&self.options, src_path, transfer).await
         }
     }
 }
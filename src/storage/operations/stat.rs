@@ -1,12 +1,13 @@
 use crate::error::Result;
-use opendal::{EntryMode, Operator};
+use chrono::{DateTime, Utc};
+use opendal::{EntryMode, Metadata, Operator};
 
 /// Object metadata used by `stat` command output.
 ///
 /// - `path`: The queried object path (as provided by caller)
 /// - `entry_type`: One of `file`, `dir`, or `other`
 /// - `size`: Content length in bytes
-/// - `last_modified`: RFC3339 string if available
+/// - `last_modified`: Last modification time if available
 /// - `etag`: Backend provided entity tag if available
 /// - `content_type`: MIME type if available
 #[derive(Debug, Clone)]
@@ -14,11 +15,31 @@ pub struct ObjectMeta {
     pub path: String,
     pub entry_type: String, // file | dir | other
     pub size: u64,
-    pub last_modified: Option<String>,
+    pub last_modified: Option<DateTime<Utc>>,
     pub etag: Option<String>,
     pub content_type: Option<String>,
 }
 
+impl ObjectMeta {
+    /// Normalize OpenDAL metadata for the given path into printable fields.
+    pub fn from_metadata(path: &str, meta: &Metadata) -> Self {
+        let entry_type = match meta.mode() {
+            EntryMode::FILE => "file".to_string(),
+            EntryMode::DIR => "dir".to_string(),
+            _ => "other".to_string(),
+        };
+
+        Self {
+            path: path.to_owned(),
+            entry_type,
+            size: meta.content_length(),
+            last_modified: meta.last_modified(),
+            etag: meta.etag().map(|s| s.to_string()),
+            content_type: meta.content_type().map(|s| s.to_string()),
+        }
+    }
+}
+
 /// Trait for fetching object metadata from storage.
 pub trait Stater {
     /// Create a new stater with the given OpenDAL operator.
@@ -49,24 +70,6 @@ impl Stater for OpenDalStater {
     /// Fetch object metadata via OpenDAL's `stat` API, and normalize fields to printable types.
     async fn stat<P: AsRef<str>>(&self, path: P) -> Result<ObjectMeta> {
         let meta = self.operator.stat(path.as_ref()).await?;
-
-        let entry_type = match meta.mode() {
-            EntryMode::FILE => "file".to_string(),
-            EntryMode::DIR => "dir".to_string(),
-            _ => "other".to_string(),
-        };
-
-        let last_modified = meta.last_modified().map(|t| t.to_string());
-        let etag = meta.etag().map(|s| s.to_string());
-        let content_type = meta.content_type().map(|s| s.to_string());
-
-        Ok(ObjectMeta {
-            path: path.as_ref().to_owned(),
-            entry_type,
-            size: meta.content_length(),
-            last_modified,
-            etag,
-            content_type,
-        })
+        Ok(ObjectMeta::from_metadata(path.as_ref(), &meta))
     }
 }
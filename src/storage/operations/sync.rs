@@ -0,0 +1,162 @@
+use crate::error::{Error, InvalidPathSnafu, PartialTransferSnafu, Result};
+use crate::storage::operations::stat::ObjectMeta;
+use crate::storage::utils::backend::Backend;
+use crate::storage::utils::options::TransferOptions;
+use crate::storage::utils::path::build_remote_path;
+use crate::storage::utils::report::{
+    Action, Failure, ItemStatus, PathRecord, TransferMethod, TransferRecord,
+};
+use crate::storage::utils::transfer::{TransferTask, is_directory, run_transfers, stream_copy};
+use crate::storage::utils::walk::{change_reason, collect_files, dir_prefix, etags_comparable};
+use snafu::ensure;
+
+/// Trait for incrementally synchronizing one storage tree into another.
+pub trait Syncer {
+    /// Make `dest_path` mirror `src_path`, transferring only new or changed files.
+    ///
+    /// # Arguments
+    /// * `src_path` - Source directory
+    /// * `dest_path` - Destination directory (created as needed)
+    /// * `delete` - Whether to delete destination files that do not exist in the source
+    ///
+    /// # Returns
+    /// * `Result<SyncReport>` - What was transferred and deleted, or detailed error information
+    async fn sync(&self, src_path: &str, dest_path: &str, delete: bool) -> Result<SyncReport>;
+}
+
+/// Outcome of a sync run.
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    /// New or changed files, copied (or planned) in completion order
+    pub copied: Vec<TransferRecord>,
+    /// Number of files that were already up to date
+    pub skipped: usize,
+    /// Destination files deleted (or planned) because they are gone from the source
+    pub deleted: Vec<PathRecord>,
+}
+
+/// Implementation of Syncer for OpenDAL Operators.
+///
+/// Either side may be a remote bucket or the local filesystem (via an `fs` operator).
+/// Only `filter`, `dry_run`, `reporter`, `progress`, `rate_limit`, `jobs` and
+/// `continue_on_error` of the options apply: files not selected by the filter are neither
+/// transferred nor deleted, and with a dry-run plan transfers and deletions are recorded
+/// instead. With `continue_on_error`, files failing to transfer or to be deleted are
+/// returned together as `Error::PartialTransfer` once the rest of the sync is done.
+pub struct OpenDalSyncer {
+    src: Backend,
+    dest: Backend,
+    options: TransferOptions,
+}
+
+impl OpenDalSyncer {
+    /// Create a new syncer reading from `src` and writing to `dest`.
+    pub fn new(src: Backend, dest: Backend, options: TransferOptions) -> Self {
+        Self { src, dest, options }
+    }
+
+    /// Stream a new or changed file to the destination. On a dry run the transfer is
+    /// only recorded.
+    async fn sync_file(&self, task: &TransferTask) -> Result<TransferRecord> {
+        if let Some(plan) = &self.options.dry_run {
+            return Ok(plan.transfer(Action::Sync, &task.src, &task.dest, task.size));
+        }
+        let total_bytes = stream_copy(
+            &self.src,
+            &task.src,
+            &self.dest,
+            &task.dest,
+            Action::Sync,
+            &self.options,
+            false,
+        )
+        .await?;
+        Ok(TransferRecord::done(
+            Action::Sync,
+            &task.src,
+            &task.dest,
+            Some(total_bytes),
+            TransferMethod::Stream,
+        ))
+    }
+}
+
+/// Decide whether a source file has to be (re)transferred.
+///
+/// A file is transferred when it is missing at the destination or has changed according
+/// to `change_reason`.
+fn needs_transfer(src: &ObjectMeta, dest: Option<&ObjectMeta>, compare_etag: bool) -> bool {
+    dest.is_none_or(|dest| change_reason(src, dest, compare_etag, false).is_some())
+}
+
+impl Syncer for OpenDalSyncer {
+    async fn sync(&self, src_path: &str, dest_path: &str, delete: bool) -> Result<SyncReport> {
+        ensure!(
+            is_directory(&self.src.operator, src_path).await,
+            InvalidPathSnafu {
+                path: src_path.to_string()
+            }
+        );
+
+        let filter = &self.options.filter;
+        let src_files = collect_files(&self.src.operator, src_path, filter).await?;
+        let dest_files = collect_files(&self.dest.operator, dest_path, filter).await?;
+        let dest_root = dir_prefix(dest_path);
+        let compare_etag = etags_comparable(&self.src.operator, &self.dest.operator);
+        let mut report = SyncReport::default();
+
+        let tasks: Vec<_> = src_files
+            .iter()
+            .filter(|(relative, src_meta)| {
+                needs_transfer(src_meta, dest_files.get(*relative), compare_etag)
+            })
+            .map(|(relative, src_meta)| {
+                let dest_key = build_remote_path(&dest_root, relative);
+                TransferTask::new(&src_meta.path, dest_key, src_meta.size)
+            })
+            .collect();
+        report.skipped = src_files.len() - tasks.len();
+
+        let transfers = run_transfers(tasks, Action::Sync, &self.options, |task| async move {
+            self.sync_file(&task).await
+        })
+        .await;
+        let mut failures = match transfers {
+            Ok(transfers) => {
+                report.copied = transfers.items;
+                Vec::new()
+            }
+            Err(Error::PartialTransfer { failures }) if self.options.continue_on_error => failures,
+            Err(e) => return Err(e),
+        };
+
+        if delete {
+            for (relative, dest_meta) in &dest_files {
+                if src_files.contains_key(relative) {
+                    continue;
+                }
+                let record = match &self.options.dry_run {
+                    Some(plan) => plan.delete(&dest_meta.path),
+                    None => match self.dest.operator.delete(&dest_meta.path).await {
+                        Ok(()) => PathRecord::new(Action::Delete, ItemStatus::Ok, &dest_meta.path),
+                        Err(e) => {
+                            let e = Error::from(e);
+                            if !self.options.continue_on_error {
+                                return Err(e);
+                            }
+                            failures.push(Failure::from_error(Action::Delete, &dest_meta.path, &e));
+                            PathRecord::failed(Action::Delete, &dest_meta.path, &e)
+                        }
+                    },
+                };
+                self.options.reporter.path(&record);
+                report.deleted.push(record);
+            }
+        }
+
+        if !failures.is_empty() {
+            return PartialTransferSnafu { failures }.fail();
+        }
+        Ok(report)
+    }
+}
@@ -1,14 +1,19 @@
 use crate::error::{DirectoryUploadNotRecursiveSnafu, PathNotFoundSnafu, Result};
-use crate::storage::constants::{DEFAULT_BUFFER_SIZE, PROGRESS_UPDATE_INTERVAL};
+use crate::storage::constants::DEFAULT_CHUNK_SIZE;
+use crate::storage::utils::checksum::{TransferHasher, verify_object};
+use crate::storage::utils::options::TransferOptions;
 use crate::storage::utils::path::build_remote_path;
-use crate::storage::utils::progress::ConsoleProgressReporter;
-use async_recursion::async_recursion;
-use opendal::Operator;
+use crate::storage::utils::report::{Action, TransferMethod, TransferRecord, TransferReport};
+use crate::storage::utils::resume::{self, ResumeState};
+use crate::storage::utils::throttle::throttle;
+use crate::storage::utils::transfer::{TransferTask, run_transfer, run_transfers};
+use opendal::{ErrorKind, Operator};
 use snafu::ensure;
 use std::ffi::OsStr;
-use std::path::Path;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
 use tokio::fs;
-use tokio::io::{AsyncReadExt, BufReader};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, BufReader};
 
 /// Trait for uploading files and directories to storage.
 pub trait Uploader {
@@ -20,78 +25,214 @@ pub trait Uploader {
     /// * `recursive` - Whether to upload directories recursively
     ///
     /// # Returns
-    /// * `Result<()>` - Success or detailed error information
-    async fn upload(&self, local_path: &str, remote_path: &str, recursive: bool) -> Result<()>;
+    /// * `Result<TransferReport>` - The uploaded files or detailed error information
+    async fn upload(
+        &self,
+        local_path: &str,
+        remote_path: &str,
+        recursive: bool,
+    ) -> Result<TransferReport>;
 }
 
 /// Implementation of Uploader for OpenDAL Operator.
 pub struct OpenDalUploader {
     operator: Operator,
+    options: TransferOptions,
 }
 
 impl OpenDalUploader {
-    /// Create a new uploader with the given OpenDAL operator.
-    pub fn new(operator: Operator) -> Self {
-        Self { operator }
+    /// Create a new uploader with the given OpenDAL operator and transfer options.
+    pub fn new(operator: Operator, options: TransferOptions) -> Self {
+        Self { operator, options }
     }
 
-    /// Upload a single file with streaming progress.
-    async fn upload_file_streaming(&self, local_path: &Path, remote_path: &str) -> Result<()> {
-        let file = fs::File::open(local_path).await?;
-        let file_size = file.metadata().await?.len();
+    /// Upload a single file with streaming, reporting its progress to `options.progress`.
+    ///
+    /// Data is written in parts of `part_size_for(file_size)` bytes, up to `concurrency`
+    /// of which are uploaded at once. Files no larger than one part are sent in a single
+    /// request when the writer is closed.
+    ///
+    /// With `resume` on a backend that supports appends, parts are appended instead so that
+    /// an interrupted upload keeps what was already stored and can continue from there.
+    ///
+    /// With `verify`, the digests of the local file are computed while streaming and
+    /// compared against the stored object's checksum once the upload completes.
+    ///
+    /// On a dry run the upload is only recorded.
+    async fn upload_file_streaming(
+        &self,
+        local_path: &Path,
+        remote_path: &str,
+    ) -> Result<TransferRecord> {
+        if let Some(plan) = &self.options.dry_run {
+            let size = fs::metadata(local_path).await?.len();
+            let src = local_path.to_string_lossy();
+            return Ok(plan.transfer(Action::Upload, &src, remote_path, size));
+        }
+        let mut file = fs::File::open(local_path).await?;
+        let local_meta = file.metadata().await?;
+        let file_size = local_meta.len();
+        let part_size = self.options.part_size_for(file_size);
+        let concurrency = self.options.concurrency.max(1);
+
+        // Bytes accepted by the writer are only stored once their part completes, so
+        // progress trails the bytes read by up to `unconfirmed`.
+        let (mut writer, offset, unconfirmed) = if self.appendable() {
+            let state = ResumeState::from_local(remote_path, &local_meta);
+            let offset = self.resume_offset(local_path, &state).await?;
+            resume::save(local_path, &state).await?;
+            if offset > 0 {
+                self.options
+                    .progress
+                    .file_resumed(&local_path.to_string_lossy(), offset);
+                file.seek(SeekFrom::Start(offset)).await?;
+            }
+            let writer = self
+                .operator
+                .writer_with(remote_path)
+                .append(true)
+                .chunk(part_size as usize)
+                .await?;
+            (writer, offset, part_size)
+        } else {
+            let writer = self
+                .operator
+                .writer_with(remote_path)
+                .chunk(part_size as usize)
+                .concurrent(concurrency)
+                .await?;
+            (writer, 0, part_size * (concurrency as u64 + 1))
+        };
+
+        let mut hasher = None;
+        if self.options.verify {
+            let mut digests = TransferHasher::default();
+            digests.update_from_file(local_path, offset).await?;
+            hasher = Some(digests);
+        }
+
         let mut reader = BufReader::new(file);
-        let mut buffer = vec![0u8; DEFAULT_BUFFER_SIZE];
-        let mut total_bytes = 0u64;
-        let mut writer = self.operator.writer(remote_path).await?;
-
-        let step_bytes = DEFAULT_BUFFER_SIZE as u64 * PROGRESS_UPDATE_INTERVAL;
-        let reporter = ConsoleProgressReporter::new(
-            format!("Uploading {}", local_path.display()),
-            Some(file_size),
-            step_bytes,
-        );
+        let mut buffer = vec![0u8; DEFAULT_CHUNK_SIZE];
+        let mut total_bytes = offset;
+
+        let src = local_path.to_string_lossy();
+        let file_progress = self
+            .options
+            .progress
+            .start_file(Action::Upload, &src, file_size);
 
         loop {
             let bytes_read = reader.read(&mut buffer).await?;
             if bytes_read == 0 {
                 break;
             }
+            if let Some(hasher) = &mut hasher {
+                hasher.update(&buffer[..bytes_read]);
+            }
             writer.write(buffer[..bytes_read].to_vec()).await?;
+            throttle(self.options.rate_limit.as_ref(), bytes_read as u64).await;
             total_bytes += bytes_read as u64;
-            reporter.maybe_report(total_bytes);
+            file_progress.set(total_bytes.saturating_sub(unconfirmed));
         }
         writer.close().await?;
-        println!(
-            "\n✅ Upload: {} → {remote_path} ({total_bytes} bytes)",
-            local_path.display(),
-        );
-        Ok(())
+        file_progress.finish(total_bytes);
+        resume::clear(local_path).await;
+        if let Some(hasher) = hasher {
+            verify_object(&self.operator, remote_path, &hasher.finish()).await?;
+        }
+        Ok(TransferRecord::done(
+            Action::Upload,
+            &src,
+            remote_path,
+            Some(total_bytes),
+            TransferMethod::Stream,
+        ))
     }
 
-    /// Upload a directory recursively.
-    #[async_recursion]
-    async fn upload_recursive(&self, local_path: &str, remote_path: &str) -> Result<()> {
-        let mut entries = fs::read_dir(local_path).await?;
-        while let Some(entry) = entries.next_entry().await? {
-            let local_file_path = entry.path();
-            let file_name = local_file_path.file_name().unwrap_or_default();
-            let file_name_str = file_name.to_string_lossy();
-            let new_remote_path = build_remote_path(remote_path, &file_name_str);
-
-            if local_file_path.is_dir() {
-                self.upload_recursive(&local_file_path.to_string_lossy(), &new_remote_path)
-                    .await?;
-            } else {
-                self.upload_file_streaming(&local_file_path, &new_remote_path)
-                    .await?;
+    /// Whether uploads go through resumable append writes.
+    fn appendable(&self) -> bool {
+        self.options.resume && self.operator.info().full_capability().write_can_append
+    }
+
+    /// Number of bytes already stored remotely that a resumed upload can keep.
+    ///
+    /// Stored bytes are only kept when the state recorded by the interrupted run still
+    /// matches the local file. Otherwise the stale object is removed, since appending to
+    /// it would corrupt the result.
+    async fn resume_offset(&self, local_path: &Path, state: &ResumeState) -> Result<u64> {
+        let stored = match self.operator.stat(&state.remote_path).await {
+            Ok(meta) => meta.content_length(),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        match resume::load(local_path).await {
+            Some(recorded) if recorded == *state && stored <= state.size => {
+                return Ok(stored);
+            }
+            Some(_) => self.options.progress.warning(&format!(
+                "{} changed since the interrupted upload; restarting",
+                local_path.display()
+            )),
+            None => self.options.progress.warning(&format!(
+                "no resume state for {}; restarting",
+                local_path.display()
+            )),
+        }
+        self.operator.delete(&state.remote_path).await?;
+        Ok(0)
+    }
+
+    /// Upload a directory recursively, transferring up to `jobs` files at a time.
+    async fn upload_recursive(
+        &self,
+        local_path: &str,
+        remote_path: &str,
+    ) -> Result<TransferReport> {
+        let mut tasks = Vec::new();
+        let mut pending = vec![(PathBuf::from(local_path), remote_path.to_string())];
+
+        while let Some((dir, remote_dir)) = pending.pop() {
+            let mut entries = fs::read_dir(&dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let local_file_path = entry.path();
+                let file_name = local_file_path.file_name().unwrap_or_default();
+                let file_name_str = file_name.to_string_lossy();
+                let new_remote_path = build_remote_path(&remote_dir, &file_name_str);
+
+                if local_file_path.is_dir() {
+                    pending.push((local_file_path, new_remote_path));
+                } else if !resume::is_partial(&local_file_path) {
+                    let relative = local_file_path
+                        .strip_prefix(local_path)
+                        .unwrap_or(&local_file_path);
+                    if !self.options.filter.matches(&relative.to_string_lossy()) {
+                        continue;
+                    }
+                    let size = entry.metadata().await?.len();
+                    tasks.push(TransferTask::new(
+                        local_file_path.to_string_lossy(),
+                        new_remote_path,
+                        size,
+                    ));
+                }
             }
         }
-        Ok(())
+
+        run_transfers(tasks, Action::Upload, &self.options, |task| async move {
+            self.upload_file_streaming(Path::new(&task.src), &task.dest)
+                .await
+        })
+        .await
     }
 }
 
 impl Uploader for OpenDalUploader {
-    async fn upload(&self, local_path: &str, remote_path: &str, recursive: bool) -> Result<()> {
+    async fn upload(
+        &self,
+        local_path: &str,
+        remote_path: &str,
+        recursive: bool,
+    ) -> Result<TransferReport> {
         let path = Path::new(local_path);
         ensure!(
             path.exists(),
@@ -99,21 +240,25 @@ impl Uploader for OpenDalUploader {
                 path: path.to_path_buf()
             }
         );
+        if self.options.resume && !self.appendable() {
+            self.options.progress.warning(&format!(
+                "{} does not support resumable uploads; uploading from the beginning",
+                self.operator.info().scheme()
+            ));
+        }
 
         if path.is_file() {
             let file_name = path.file_name().unwrap_or(OsStr::new(local_path));
             let file_name_str = file_name.to_string_lossy();
             let remote_file_path = build_remote_path(remote_path, &file_name_str);
-            self.upload_file_streaming(Path::new(local_path), &remote_file_path)
-                .await?;
+            let transfer = self.upload_file_streaming(Path::new(local_path), &remote_file_path);
+            run_transfer(&self.options, local_path, transfer).await
+        } else if path.is_dir() && recursive {
+            self.upload_recursive(local_path, remote_path).await
         } else if path.is_dir() {
-            if recursive {
-                self.upload_recursive(local_path, remote_path).await?;
-            } else {
-                return DirectoryUploadNotRecursiveSnafu.fail();
-            }
+            DirectoryUploadNotRecursiveSnafu.fail()
+        } else {
+            Ok(TransferReport::default())
         }
-
-        Ok(())
     }
 }
@@ -1,4 +1,7 @@
 use crate::error::Result;
+use crate::storage::utils::filter::PathFilter;
+use crate::storage::utils::path::get_root_relative_path;
+use crate::storage::utils::report::{Item, Reporter, UsageEntry};
 use futures::stream::TryStreamExt;
 use opendal::Operator;
 
@@ -8,49 +11,79 @@ pub trait UsageCalculator {
     ///
     /// # Arguments
     /// * `path` - Path to calculate usage for
-    /// * `summary` - Whether to show summary only or detailed listing
+    /// * `summary` - Whether to compute the total only, without per-entry usage
     ///
     /// # Returns
-    /// * `Result<()>` - Success or detailed error information
-    async fn calculate_usage(&self, path: &str, summary: bool) -> Result<()>;
+    /// * `Result<UsageReport>` - Usage of `path` or detailed error information
+    async fn calculate_usage(&self, path: &str, summary: bool) -> Result<UsageReport>;
+}
+
+/// Disk usage under a path.
+#[derive(Debug, Clone)]
+pub struct UsageReport {
+    /// Total size and number of entries under the path
+    pub total: UsageEntry,
+    /// Usage of every entry, unless only a summary was requested
+    pub entries: Vec<UsageEntry>,
 }
 
 /// Implementation of UsageCalculator for OpenDAL Operator.
+///
+/// Only files selected by `filter` are counted. The usage of every entry is passed to
+/// `reporter` as soon as it is listed.
 pub struct OpenDalUsageCalculator {
     operator: Operator,
+    filter: PathFilter,
+    reporter: Reporter,
 }
 
 impl OpenDalUsageCalculator {
-    /// Create a new usage calculator with the given OpenDAL operator.
-    pub fn new(operator: Operator) -> Self {
-        Self { operator }
+    /// Create a new usage calculator with the given OpenDAL operator, file filter and reporter.
+    pub fn new(operator: Operator, filter: PathFilter, reporter: Reporter) -> Self {
+        Self {
+            operator,
+            filter,
+            reporter,
+        }
     }
 }
 
 impl UsageCalculator for OpenDalUsageCalculator {
-    async fn calculate_usage(&self, path: &str, summary: bool) -> Result<()> {
+    async fn calculate_usage(&self, path: &str, summary: bool) -> Result<UsageReport> {
         let lister = self.operator.lister_with(path).recursive(true).await?;
-        let (total_size, total_files) = lister
-            .try_fold((0, 0), |(size, count), entry| async move {
-                let meta = entry.metadata();
-                if !summary {
-                    println!(
-                        "{} {}",
-                        crate::storage::utils::size::format_size(meta.content_length()),
-                        entry.path()
-                    );
-                }
-                Ok((size + meta.content_length(), count + 1))
-            })
+        let (total_size, total_files, entries) = lister
+            .try_fold(
+                (0, 0, Vec::new()),
+                |(size, count, mut entries), entry| async move {
+                    let meta = entry.metadata();
+                    if !meta.mode().is_dir()
+                        && !self
+                            .filter
+                            .matches(&get_root_relative_path(entry.path(), path))
+                    {
+                        return Ok((size, count, entries));
+                    }
+                    if !summary {
+                        let usage = UsageEntry {
+                            path: entry.path().to_string(),
+                            size: meta.content_length(),
+                            files: 1,
+                        };
+                        self.reporter.report(Item::Usage(&usage));
+                        entries.push(usage);
+                    }
+                    Ok((size + meta.content_length(), count + 1, entries))
+                },
+            )
             .await?;
 
-        if summary {
-            println!(
-                "{} {path}",
-                crate::storage::utils::size::format_size(total_size)
-            );
-            println!("Total files: {total_files}");
-        }
-        Ok(())
+        Ok(UsageReport {
+            total: UsageEntry {
+                path: path.to_string(),
+                size: total_size,
+                files: total_files,
+            },
+            entries,
+        })
     }
 }
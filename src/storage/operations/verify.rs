@@ -0,0 +1,154 @@
+use crate::error::{Error, Result};
+use crate::storage::utils::checksum::{Checksum, ChecksumAlgo, ContentHasher, verify_object};
+use crate::storage::utils::filter::PathFilter;
+use crate::storage::utils::walk::{collect_files, collect_local_files};
+use opendal::Operator;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// A file present on both sides whose contents do not match.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDifference {
+    /// Path relative to the compared roots
+    pub path: String,
+    /// What differs: `size` or `md5`
+    pub field: String,
+    /// Local value
+    pub local: String,
+    /// Remote value
+    pub remote: String,
+}
+
+/// Outcome of comparing a local tree with a remote prefix.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerifyReport {
+    /// Number of files that exist on both sides and match
+    pub matched: usize,
+    /// Local files that are absent remotely
+    pub missing: Vec<String>,
+    /// Remote files that are absent locally
+    pub extra: Vec<String>,
+    /// Files present on both sides with different contents
+    pub differing: Vec<FileDifference>,
+}
+
+impl VerifyReport {
+    /// Whether both trees hold the same files with the same contents.
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.differing.is_empty()
+    }
+}
+
+/// Trait for verifying that remote storage mirrors a local directory tree.
+pub trait Verifier {
+    /// Compare a local directory with a remote prefix.
+    ///
+    /// # Arguments
+    /// * `local_path` - Local directory that was uploaded
+    /// * `remote_path` - Remote prefix expected to mirror it
+    /// * `checksum` - Also compare MD5 content hashes of files with equal sizes
+    ///
+    /// # Returns
+    /// * `Result<VerifyReport>` - Missing, extra and differing files
+    async fn verify(
+        &self,
+        local_path: &str,
+        remote_path: &str,
+        checksum: bool,
+    ) -> Result<VerifyReport>;
+}
+
+/// Implementation of Verifier for OpenDAL Operator.
+///
+/// Only files selected by `filter` are compared, on both sides.
+pub struct OpenDalVerifier {
+    operator: Operator,
+    filter: PathFilter,
+}
+
+impl OpenDalVerifier {
+    /// Create a new verifier with the given OpenDAL operator and file filter.
+    pub fn new(operator: Operator, filter: PathFilter) -> Self {
+        Self { operator, filter }
+    }
+}
+
+impl Verifier for OpenDalVerifier {
+    async fn verify(
+        &self,
+        local_path: &str,
+        remote_path: &str,
+        checksum: bool,
+    ) -> Result<VerifyReport> {
+        let local_root = Path::new(local_path);
+        if !local_root.is_dir() {
+            return Err(if local_root.exists() {
+                Error::InvalidPath {
+                    path: local_path.to_string(),
+                }
+            } else {
+                Error::PathNotFound {
+                    path: PathBuf::from(local_path),
+                }
+            });
+        }
+
+        let local_files = collect_local_files(local_root, &self.filter).await?;
+        let remote_files = collect_files(&self.operator, remote_path, &self.filter).await?;
+        let mut report = VerifyReport::default();
+
+        for (relative, local) in &local_files {
+            let Some(remote) = remote_files.get(relative) else {
+                report.missing.push(relative.clone());
+                continue;
+            };
+
+            if local.size != remote.size {
+                report.differing.push(FileDifference {
+                    path: relative.clone(),
+                    field: "size".to_string(),
+                    local: local.size.to_string(),
+                    remote: remote.size.to_string(),
+                });
+                continue;
+            }
+
+            if checksum {
+                let mut hasher = ContentHasher::new(ChecksumAlgo::Md5);
+                hasher.update_from_file(&local.path, local.size).await?;
+                let local_digest = Checksum {
+                    algo: ChecksumAlgo::Md5,
+                    value: hasher.finish(),
+                };
+                match verify_object(&self.operator, &remote.path, &[local_digest]).await {
+                    Ok(()) => {}
+                    Err(Error::ChecksumMismatch {
+                        algo,
+                        expected,
+                        actual,
+                        ..
+                    }) => {
+                        report.differing.push(FileDifference {
+                            path: relative.clone(),
+                            field: algo.to_string(),
+                            local: actual,
+                            remote: expected,
+                        });
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+
+            report.matched += 1;
+        }
+
+        report.extra = remote_files
+            .keys()
+            .filter(|relative| !local_files.contains_key(*relative))
+            .cloned()
+            .collect();
+
+        Ok(report)
+    }
+}
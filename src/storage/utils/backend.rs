@@ -0,0 +1,29 @@
+// Storage reached by an operation
+use crate::storage::StorageConfig;
+use opendal::Operator;
+
+/// An OpenDAL operator together with what OpenDAL does not expose about the storage
+/// it reaches: where the service lives.
+#[derive(Clone)]
+pub struct Backend {
+    pub operator: Operator,
+    /// The same storage without the request timeout, for server-side copies and renames,
+    /// whose duration grows with the size of the object
+    pub server_side: Operator,
+    /// Endpoint of the service, when configured
+    pub endpoint: Option<String>,
+    /// Region of the service, when configured
+    pub region: Option<String>,
+}
+
+impl Backend {
+    /// Describe the storage `operator` and `server_side` were built for from `config`.
+    pub fn new(operator: Operator, server_side: Operator, config: &StorageConfig) -> Self {
+        Self {
+            operator,
+            server_side,
+            endpoint: config.endpoint.clone(),
+            region: config.region.clone(),
+        }
+    }
+}
@@ -0,0 +1,249 @@
+// Content checksums used by the checksum command and transfer verification
+use crate::error::{ChecksumMismatchSnafu, Error, Result};
+use crate::storage::constants::DEFAULT_CHUNK_SIZE;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use futures::stream::TryStreamExt;
+use md5::{Digest, Md5};
+use opendal::Operator;
+use sha2::Sha256;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use tokio::fs;
+use tokio::io::{AsyncReadExt, BufReader};
+
+/// Hash algorithms available for content checksums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgo {
+    Md5,
+    Sha256,
+    Crc32c,
+}
+
+impl FromStr for ChecksumAlgo {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "md5" => Ok(Self::Md5),
+            "sha256" | "sha-256" => Ok(Self::Sha256),
+            "crc32c" => Ok(Self::Crc32c),
+            _ => Err(Error::InvalidArgument {
+                name: "--algo".to_string(),
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for ChecksumAlgo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Md5 => "md5",
+            Self::Sha256 => "sha256",
+            Self::Crc32c => "crc32c",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A digest of some content, with the algorithm that produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checksum {
+    pub algo: ChecksumAlgo,
+    /// Lowercase hex digest
+    pub value: String,
+}
+
+/// Incremental hash of the bytes streamed through a transfer or checksum run.
+#[derive(Clone)]
+pub enum ContentHasher {
+    Md5(Md5),
+    Sha256(Sha256),
+    Crc32c(u32),
+}
+
+impl ContentHasher {
+    pub fn new(algo: ChecksumAlgo) -> Self {
+        match algo {
+            ChecksumAlgo::Md5 => Self::Md5(Md5::new()),
+            ChecksumAlgo::Sha256 => Self::Sha256(Sha256::new()),
+            ChecksumAlgo::Crc32c => Self::Crc32c(0),
+        }
+    }
+
+    pub fn algo(&self) -> ChecksumAlgo {
+        match self {
+            Self::Md5(_) => ChecksumAlgo::Md5,
+            Self::Sha256(_) => ChecksumAlgo::Sha256,
+            Self::Crc32c(_) => ChecksumAlgo::Crc32c,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Md5(hasher) => hasher.update(data),
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Crc32c(crc) => *crc = crc32c::crc32c_append(*crc, data),
+        }
+    }
+
+    /// Feed the first `len` bytes of a local file, e.g. the part kept by a resumed transfer.
+    pub async fn update_from_file(&mut self, path: &Path, len: u64) -> Result<()> {
+        read_prefix(path, len, |data| self.update(data)).await
+    }
+
+    /// Lowercase hex digest of everything fed so far.
+    pub fn finish(self) -> String {
+        match self {
+            Self::Md5(hasher) => hex(&hasher.finalize()),
+            Self::Sha256(hasher) => hex(&hasher.finalize()),
+            Self::Crc32c(crc) => format!("{crc:08x}"),
+        }
+    }
+}
+
+/// Digests of a transfer's content in every algorithm a backend may advertise, so that
+/// whichever checksum the stored copy offers can be compared. Backends only advertise
+/// MD5 digests (`Content-MD5` or the ETag).
+#[derive(Clone)]
+pub struct TransferHasher(Vec<ContentHasher>);
+
+impl Default for TransferHasher {
+    fn default() -> Self {
+        Self(
+            [ChecksumAlgo::Md5]
+                .into_iter()
+                .map(ContentHasher::new)
+                .collect(),
+        )
+    }
+}
+
+impl TransferHasher {
+    pub fn update(&mut self, data: &[u8]) {
+        for hasher in &mut self.0 {
+            hasher.update(data);
+        }
+    }
+
+    /// Feed the first `len` bytes of a local file, e.g. the part kept by a resumed transfer.
+    pub async fn update_from_file(&mut self, path: &Path, len: u64) -> Result<()> {
+        read_prefix(path, len, |data| self.update(data)).await
+    }
+
+    /// Digests of everything fed so far, one per algorithm.
+    pub fn finish(self) -> Vec<Checksum> {
+        self.0
+            .into_iter()
+            .map(|hasher| Checksum {
+                algo: hasher.algo(),
+                value: hasher.finish(),
+            })
+            .collect()
+    }
+}
+
+/// Pass the first `len` bytes of the file at `path` to `update`, chunk by chunk.
+async fn read_prefix(path: &Path, len: u64, mut update: impl FnMut(&[u8])) -> Result<()> {
+    let file = fs::File::open(path).await?;
+    let mut reader = BufReader::new(file.take(len));
+    let mut buffer = vec![0u8; DEFAULT_CHUNK_SIZE];
+    loop {
+        let bytes_read = reader.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            break;
+        }
+        update(&buffer[..bytes_read]);
+    }
+    Ok(())
+}
+
+/// Lowercase hexadecimal representation of `bytes`.
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// MD5 the backend advertises for the object at `path`, if any.
+///
+/// `Content-MD5` (hex or base64) is preferred. Otherwise the ETag is used when it looks
+/// like a plain MD5 digest, which rules out multipart uploads (`<md5>-<parts>`). Such an
+/// ETag is still not an MD5 for objects encrypted with SSE-KMS or SSE-C, so an advertised
+/// checksum proves a match but not a mismatch; see `verify_object`.
+pub async fn advertised_checksum(operator: &Operator, path: &str) -> Result<Option<Checksum>> {
+    let meta = operator.stat(path).await?;
+    Ok(meta
+        .content_md5()
+        .and_then(normalize_md5)
+        .or_else(|| meta.etag().and_then(normalize_md5))
+        .map(|value| Checksum {
+            algo: ChecksumAlgo::Md5,
+            value,
+        }))
+}
+
+/// MD5 digest carried by an ETag or Content-MD5 value (hex or base64), if it is one.
+pub fn normalize_md5(value: &str) -> Option<String> {
+    let value = value.trim().trim_start_matches("W/").trim_matches('"');
+    if value.len() == 32 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Some(value.to_ascii_lowercase());
+    }
+    match STANDARD.decode(value) {
+        Ok(bytes) if bytes.len() == 16 => Some(hex(&bytes)),
+        _ => None,
+    }
+}
+
+/// Compute the checksum of a stored object by streaming it.
+pub async fn compute_checksum(
+    operator: &Operator,
+    path: &str,
+    algo: ChecksumAlgo,
+) -> Result<String> {
+    let mut stream = operator
+        .reader_with(path)
+        .chunk(DEFAULT_CHUNK_SIZE)
+        .await?
+        .into_bytes_stream(..)
+        .await?;
+    let mut hasher = ContentHasher::new(algo);
+    while let Some(bytes) = stream.try_next().await? {
+        hasher.update(&bytes);
+    }
+    Ok(hasher.finish())
+}
+
+/// Check that the object stored at `path` matches the content's `digests`.
+///
+/// The advertised checksum is compared first. When it is missing or differs, the object
+/// is read back and its MD5 compared instead, since an advertised ETag need not be an MD5.
+pub async fn verify_object(operator: &Operator, path: &str, digests: &[Checksum]) -> Result<()> {
+    if let Some(advertised) = advertised_checksum(operator, path).await?
+        && ensure_match(path, &advertised, digests).is_ok()
+    {
+        return Ok(());
+    }
+    let stored = Checksum {
+        algo: ChecksumAlgo::Md5,
+        value: compute_checksum(operator, path, ChecksumAlgo::Md5).await?,
+    };
+    ensure_match(path, &stored, digests)
+}
+
+/// Fail with `ChecksumMismatch` unless `digests` holds the same digest as `stored`.
+pub fn ensure_match(path: &str, stored: &Checksum, digests: &[Checksum]) -> Result<()> {
+    let actual = digests.iter().find(|digest| digest.algo == stored.algo);
+    if actual.is_some_and(|actual| actual.value == stored.value) {
+        return Ok(());
+    }
+    ChecksumMismatchSnafu {
+        path: path.to_string(),
+        algo: stored.algo,
+        expected: stored.value.clone(),
+        actual: actual
+            .map(|actual| actual.value.clone())
+            .unwrap_or_default(),
+    }
+    .fail()
+}
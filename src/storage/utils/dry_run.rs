@@ -0,0 +1,51 @@
+// Planning of mutating operations run with --dry-run
+use crate::storage::utils::report::{Action, ItemStatus, PathRecord, TransferRecord};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// Changes collected by a dry run in place of the writes, moves and deletions
+/// the operations would have issued.
+///
+/// Operations walk their sources exactly as in a real run and record every change
+/// here instead of performing it. Clones share the same totals, so a single plan can
+/// span all the operations of one command.
+#[derive(Debug, Clone, Default)]
+pub struct DryRun {
+    totals: Arc<Mutex<BTreeMap<Action, (usize, u64)>>>,
+}
+
+impl DryRun {
+    /// Record a file that would be transferred from `src` to `dest`.
+    pub fn transfer(&self, action: Action, src: &str, dest: &str, size: u64) -> TransferRecord {
+        self.add(action, size);
+        TransferRecord::planned(action, src, dest, size)
+    }
+
+    /// Record a path that would be deleted.
+    pub fn delete(&self, path: &str) -> PathRecord {
+        self.add(Action::Delete, 0);
+        PathRecord::new(Action::Delete, ItemStatus::Planned, path)
+    }
+
+    /// Record a directory that would be created.
+    pub fn mkdir(&self, path: &str) -> PathRecord {
+        self.add(Action::Mkdir, 0);
+        PathRecord::new(Action::Mkdir, ItemStatus::Planned, path)
+    }
+
+    fn add(&self, action: Action, size: u64) {
+        let mut totals = self.totals.lock().unwrap();
+        let (count, bytes) = totals.entry(action).or_default();
+        *count += 1;
+        *bytes += size;
+    }
+
+    /// Number of changes and their bytes recorded so far, by kind.
+    pub fn totals(&self) -> Vec<(Action, usize, u64)> {
+        let totals = self.totals.lock().unwrap();
+        totals
+            .iter()
+            .map(|(action, (count, bytes))| (*action, *count, *bytes))
+            .collect()
+    }
+}
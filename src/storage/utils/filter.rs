@@ -0,0 +1,91 @@
+// Include/exclude glob filters applied by recursive walkers
+use crate::error::{Error, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+/// Selects which files a recursive operation acts on.
+///
+/// Patterns are matched against a file's path relative to the walked root. A pattern
+/// without `/` matches the file name at any depth (`*.log`); one with `/` matches the
+/// whole relative path (`logs/**/*.gz`). `*` never crosses a `/`, `**` does.
+///
+/// When include patterns are given, only files matching one of them are selected.
+/// Files matching any exclude pattern are always skipped.
+#[derive(Debug, Clone, Default)]
+pub struct PathFilter {
+    include: Option<Patterns>,
+    exclude: Option<Patterns>,
+}
+
+/// Compiled patterns, split by what they are matched against.
+#[derive(Debug, Clone)]
+struct Patterns {
+    names: GlobSet,
+    paths: GlobSet,
+}
+
+impl Patterns {
+    fn new(option: &str, patterns: &[String]) -> Result<Option<Self>> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+        let invalid = |value: &str| Error::InvalidArgument {
+            name: option.to_string(),
+            value: value.to_string(),
+        };
+        let mut names = GlobSetBuilder::new();
+        let mut paths = GlobSetBuilder::new();
+        for pattern in patterns {
+            let trimmed = pattern.trim_start_matches('/');
+            let glob = GlobBuilder::new(trimmed)
+                .literal_separator(true)
+                .build()
+                .map_err(|_| invalid(pattern))?;
+            if trimmed.contains('/') {
+                paths.add(glob);
+            } else {
+                names.add(glob);
+            }
+        }
+        Ok(Some(Self {
+            names: names.build().map_err(|_| invalid(&patterns.join(",")))?,
+            paths: paths.build().map_err(|_| invalid(&patterns.join(",")))?,
+        }))
+    }
+
+    fn is_match(&self, relative: &str) -> bool {
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        self.names.is_match(name) || self.paths.is_match(relative)
+    }
+}
+
+impl PathFilter {
+    /// Compile include and exclude patterns.
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The filter, or `InvalidArgument` for a malformed pattern
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: Patterns::new("--include", include)?,
+            exclude: Patterns::new("--exclude", exclude)?,
+        })
+    }
+
+    /// Whether the filter selects every file.
+    pub fn is_empty(&self) -> bool {
+        self.include.is_none() && self.exclude.is_none()
+    }
+
+    /// Whether the file at `relative` (relative to the walked root) is selected.
+    pub fn matches(&self, relative: &str) -> bool {
+        let relative = relative.trim_start_matches('/');
+        if let Some(include) = &self.include
+            && !include.is_match(relative)
+        {
+            return false;
+        }
+        !self
+            .exclude
+            .as_ref()
+            .is_some_and(|exclude| exclude.is_match(relative))
+    }
+}
@@ -0,0 +1,67 @@
+// Expansion of glob patterns in remote path arguments
+use crate::error::{Error, NoGlobMatchSnafu, Result};
+use futures::stream::TryStreamExt;
+use globset::GlobBuilder;
+use opendal::{ErrorKind, Operator};
+use snafu::ensure;
+
+/// Characters that make a path argument a glob pattern.
+const GLOB_META: &[char] = &['*', '?', '['];
+
+/// Whether `path` contains glob metacharacters.
+pub fn has_glob(path: &str) -> bool {
+    path.contains(GLOB_META)
+}
+
+/// Expand a glob pattern into the matching remote paths, sorted.
+///
+/// Only the longest literal directory prefix of the pattern is listed, recursively when
+/// the rest of the pattern spans directories (contains `/` or `**`). `*` and `?` never
+/// match `/`. A pattern ending in `/` only matches directories; recursive patterns only
+/// match files. Paths without metacharacters are returned unchanged.
+pub async fn expand_glob(operator: &Operator, pattern: &str) -> Result<Vec<String>> {
+    if !has_glob(pattern) {
+        return Ok(vec![pattern.to_string()]);
+    }
+
+    let dirs_only = pattern.ends_with('/');
+    let normalized = pattern.trim_start_matches('/').trim_end_matches('/');
+    let first_meta = normalized.find(GLOB_META).unwrap_or(normalized.len());
+    let prefix_len = normalized[..first_meta].rfind('/').map_or(0, |i| i + 1);
+    let (prefix, rest) = normalized.split_at(prefix_len);
+    let recursive = rest.contains('/') || rest.contains("**");
+
+    let matcher = GlobBuilder::new(normalized)
+        .literal_separator(true)
+        .build()
+        .map_err(|_| Error::InvalidPath {
+            path: pattern.to_string(),
+        })?
+        .compile_matcher();
+
+    let list_root = if prefix.is_empty() { "/" } else { prefix };
+    let mut lister = match operator.lister_with(list_root).recursive(recursive).await {
+        Ok(lister) => lister,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            return NoGlobMatchSnafu { pattern }.fail();
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut matches = Vec::new();
+    while let Some(entry) = lister.try_next().await? {
+        let is_dir = entry.metadata().mode().is_dir();
+        if (recursive && is_dir) || (dirs_only && !is_dir) {
+            continue;
+        }
+        let candidate = entry.path().trim_start_matches('/').trim_end_matches('/');
+        if matcher.is_match(candidate) {
+            matches.push(entry.path().to_string());
+        }
+    }
+
+    ensure!(!matches.is_empty(), NoGlobMatchSnafu { pattern });
+    matches.sort();
+    matches.dedup();
+    Ok(matches)
+}
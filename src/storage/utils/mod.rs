@@ -1,8 +1,22 @@
 // Utilities for storage module
+pub mod backend;
+pub mod checksum;
+pub mod dry_run;
 pub mod error;
+pub mod filter;
+pub mod glob;
+pub mod options;
 pub mod path;
 pub mod progress;
+pub mod report;
+pub mod resume;
 pub mod size;
+pub mod throttle;
+pub mod transfer;
+pub mod walk;
+
+use crate::error::{Error, Result};
+use std::str::FromStr;
 
 /// Output format for CLI commands that can render machine-readable results
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -13,4 +27,23 @@ pub enum OutputFormat {
     Raw,
     /// Single-line JSON output
     Json,
+    /// One JSON object per line, printed as results arrive
+    Jsonl,
+}
+
+impl FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "human" => Ok(Self::Human),
+            "raw" => Ok(Self::Raw),
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::Jsonl),
+            _ => Err(Error::InvalidArgument {
+                name: "--output".to_string(),
+                value: s.to_string(),
+            }),
+        }
+    }
 }
@@ -0,0 +1,71 @@
+// Options shared by transfer operations
+use crate::storage::constants::{
+    DEFAULT_JOBS, DEFAULT_PART_CONCURRENCY, DEFAULT_PART_SIZE, MAX_PART_COUNT,
+};
+use crate::storage::utils::dry_run::DryRun;
+use crate::storage::utils::filter::PathFilter;
+use crate::storage::utils::progress::Progress;
+use crate::storage::utils::report::Reporter;
+use crate::storage::utils::throttle::RateLimiter;
+
+/// Tuning knobs for transfer operations (`put`, `get`, `cp`, `mv`).
+#[derive(Debug, Clone)]
+pub struct TransferOptions {
+    /// Maximum number of files transferred concurrently during recursive operations.
+    pub jobs: usize,
+    /// Part size for multipart uploads and ranged downloads; chosen from the file size when `None`.
+    pub part_size: Option<u64>,
+    /// Maximum number of parts of a single file transferred concurrently.
+    pub concurrency: usize,
+    /// Continue interrupted transfers instead of starting over.
+    pub resume: bool,
+    /// Verify the MD5 of every transferred file against the stored copy.
+    pub verify: bool,
+    /// Use server-side copy/rename when source and destination share a storage.
+    pub server_side: bool,
+    /// Files of recursive transfers to act on, by path relative to the source root.
+    pub filter: PathFilter,
+    /// Record the files that would be transferred instead of transferring them.
+    pub dry_run: Option<DryRun>,
+    /// Receives the result of every file as soon as it is transferred.
+    pub reporter: Reporter,
+    /// Receives progress events while files are transferred; when not set, the observer of
+    /// the client: the console, unless the client is quiet (`StorageClient::quiet`) or
+    /// was given another one (`StorageClient::with_progress_observer`).
+    pub progress: Progress,
+    /// Caps the combined throughput of every transfer sharing the limiter.
+    pub rate_limit: Option<RateLimiter>,
+    /// Keep transferring the other files of a recursive operation after one failed.
+    pub continue_on_error: bool,
+}
+
+impl Default for TransferOptions {
+    fn default() -> Self {
+        Self {
+            jobs: DEFAULT_JOBS,
+            part_size: None,
+            concurrency: DEFAULT_PART_CONCURRENCY,
+            resume: false,
+            verify: false,
+            server_side: true,
+            filter: PathFilter::default(),
+            dry_run: None,
+            reporter: Reporter::default(),
+            progress: Progress::default(),
+            rate_limit: None,
+            continue_on_error: false,
+        }
+    }
+}
+
+impl TransferOptions {
+    /// Part size to use when transferring a file of `file_size` bytes.
+    ///
+    /// The requested (or default) part size is raised when needed so that the upload
+    /// stays within the provider limit of `MAX_PART_COUNT` parts.
+    pub fn part_size_for(&self, file_size: u64) -> u64 {
+        let requested = self.part_size.unwrap_or(DEFAULT_PART_SIZE);
+        let required = file_size.div_ceil(MAX_PART_COUNT);
+        requested.max(required)
+    }
+}
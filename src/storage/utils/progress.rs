@@ -1,32 +1,373 @@
-use std::io::{self, Write};
+// Progress events of transfer jobs and their console rendering on stderr
+use crate::error::Error;
+use crate::storage::utils::report::Action;
+use crate::storage::utils::size::format_size;
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-/// A minimal progress reporter that prints percentage updates to stdout.
-pub struct ConsoleProgressReporter {
+/// Minimum time between two redraws of the progress line.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Time a transfer has to run before its throughput is considered meaningful.
+const RATE_WARMUP: Duration = Duration::from_millis(500);
+
+/// A change in the progress of a transfer job, as delivered to a `ProgressObserver`.
+///
+/// Files are identified by their source path. Every job starts with `JobStarted` and
+/// ends with `JobFinished`; files that start in between end with either `FileFinished`
+/// or `FileFailed`. `FileResumed` and `Warning` are notes for the user, which may arrive
+/// at any time.
+#[derive(Debug, Clone, Copy)]
+pub enum ProgressEvent<'a> {
+    /// A job of `files` files totalling `bytes` bytes, as listed, is starting.
+    /// Listed sizes may be missing, so `bytes` can be an underestimate.
+    JobStarted { files: usize, bytes: u64 },
+    /// The transfer of a file of `size` bytes is starting.
+    FileStarted {
+        action: Action,
+        path: &'a str,
+        size: u64,
+    },
+    /// `bytes` bytes of the file have been transferred so far.
+    BytesTransferred { path: &'a str, bytes: u64 },
+    /// The file has been transferred completely.
+    FileFinished { path: &'a str, bytes: u64 },
+    /// The transfer of the file failed.
+    FileFailed { path: &'a str, error: &'a str },
+    /// The file continues an interrupted transfer from byte `offset`.
+    FileResumed { path: &'a str, offset: u64 },
+    /// Something the user should know about that does not stop the transfer, e.g. an
+    /// interrupted transfer that has to start over.
+    Warning { message: &'a str },
+    /// The job is over.
+    JobFinished,
+}
+
+/// Receives progress events while transfers run.
+///
+/// Events are delivered from the tasks doing the transfers, several of which may run
+/// at once, so observers should return quickly.
+pub trait ProgressObserver: Send + Sync {
+    fn on_event(&self, event: ProgressEvent<'_>);
+}
+
+/// Handle through which operations deliver progress events to an optional `ProgressObserver`.
+#[derive(Clone, Default)]
+pub struct Progress {
+    observer: Option<Arc<dyn ProgressObserver>>,
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("observed", &self.observer.is_some())
+            .finish()
+    }
+}
+
+impl Progress {
+    pub fn new(observer: Arc<dyn ProgressObserver>) -> Self {
+        Self {
+            observer: Some(observer),
+        }
+    }
+
+    /// Progress drawn on stderr by a `ConsoleProgress`.
+    pub fn console() -> Self {
+        Self::new(Arc::new(ConsoleProgress::new()))
+    }
+
+    /// Whether anyone receives the events.
+    pub fn is_enabled(&self) -> bool {
+        self.observer.is_some()
+    }
+
+    pub fn event(&self, event: ProgressEvent<'_>) {
+        if let Some(observer) = &self.observer {
+            observer.on_event(event);
+        }
+    }
+
+    pub fn start_job(&self, files: usize, bytes: u64) {
+        self.event(ProgressEvent::JobStarted { files, bytes });
+    }
+
+    pub fn finish_job(&self) {
+        self.event(ProgressEvent::JobFinished);
+    }
+
+    /// Announce the transfer of a file and return the handle to follow it with.
+    pub fn start_file(&self, action: Action, path: &str, size: u64) -> FileProgress {
+        self.event(ProgressEvent::FileStarted { action, path, size });
+        FileProgress {
+            progress: self.clone(),
+            path: path.to_string(),
+        }
+    }
+
+    pub fn file_failed(&self, path: &str, error: &Error) {
+        let error = error.to_string();
+        self.event(ProgressEvent::FileFailed {
+            path,
+            error: &error,
+        });
+    }
+
+    pub fn file_resumed(&self, path: &str, offset: u64) {
+        self.event(ProgressEvent::FileResumed { path, offset });
+    }
+
+    pub fn warning(&self, message: &str) {
+        self.event(ProgressEvent::Warning { message });
+    }
+}
+
+/// Progress of a single file within a job.
+#[derive(Debug)]
+pub struct FileProgress {
+    progress: Progress,
+    path: String,
+}
+
+impl FileProgress {
+    /// Record that `bytes` bytes of the file have been transferred so far.
+    pub fn set(&self, bytes: u64) {
+        self.progress.event(ProgressEvent::BytesTransferred {
+            path: &self.path,
+            bytes,
+        });
+    }
+
+    /// Record that the file has been transferred completely.
+    pub fn finish(self, bytes: u64) {
+        self.progress.event(ProgressEvent::FileFinished {
+            path: &self.path,
+            bytes,
+        });
+    }
+}
+
+/// Whether a progress line is currently drawn on stderr.
+static LINE_DRAWN: Mutex<bool> = Mutex::new(false);
+
+/// Erase the progress line, if one is drawn, so that other output starts on a clean line.
+///
+/// The line is redrawn on the next refresh.
+pub fn clear_progress_line() {
+    let mut drawn = LINE_DRAWN.lock().unwrap();
+    if *drawn {
+        let mut stderr = io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K");
+        let _ = stderr.flush();
+        *drawn = false;
+    }
+}
+
+fn draw_line(line: &str) {
+    let mut drawn = LINE_DRAWN.lock().unwrap();
+    let mut stderr = io::stderr().lock();
+    let _ = write!(stderr, "\r\x1b[2K{line}");
+    let _ = stderr.flush();
+    *drawn = true;
+}
+
+/// Default `ProgressObserver`, drawing a single line on stderr refreshed over time.
+///
+/// Jobs of several files show an aggregate line with the files and bytes done out of the
+/// planned totals; a single file shows its own label. Both include the throughput and an
+/// estimate of the remaining time. Nothing is drawn unless stderr is a terminal. One job
+/// is followed at a time: a new job resets the totals.
+#[derive(Debug)]
+pub struct ConsoleProgress {
+    enabled: bool,
+    state: Mutex<JobState>,
+}
+
+#[derive(Debug)]
+struct JobState {
+    files_total: usize,
+    bytes_total: u64,
+    files_done: usize,
+    /// Bytes of the files already finished
+    bytes_finished: u64,
+    active: Vec<ActiveFile>,
+    started: Instant,
+    last_draw: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct ActiveFile {
+    path: String,
     label: String,
-    total_bytes: Option<u64>,
-    step_bytes: u64,
+    size: u64,
+    done: u64,
+}
+
+impl JobState {
+    fn new(files_total: usize, bytes_total: u64) -> Self {
+        Self {
+            files_total,
+            bytes_total,
+            files_done: 0,
+            bytes_finished: 0,
+            active: Vec::new(),
+            started: Instant::now(),
+            last_draw: None,
+        }
+    }
+
+    fn bytes_done(&self) -> u64 {
+        self.bytes_finished + self.active.iter().map(|file| file.done).sum::<u64>()
+    }
+
+    /// Bytes per second since the job started, once it ran long enough to tell.
+    fn rate(&self, now: Instant) -> Option<f64> {
+        let elapsed = now.duration_since(self.started);
+        (elapsed >= RATE_WARMUP).then(|| self.bytes_done() as f64 / elapsed.as_secs_f64())
+    }
+
+    fn line(&self, now: Instant) -> Option<String> {
+        let done = self.bytes_done();
+        let (label, total, percent) = if self.files_total > 1 {
+            // Listed sizes can be missing, so the total never drops below what is done.
+            let total = self.bytes_total.max(done);
+            let percent = if self.bytes_total > 0 {
+                percent(done, total)
+            } else {
+                percent(self.files_done as u64, self.files_total as u64)
+            };
+            let label = format!("[{}/{} files]", self.files_done, self.files_total);
+            (label, total, percent)
+        } else {
+            let file = self.active.first()?;
+            (file.label.clone(), file.size, percent(file.done, file.size))
+        };
+
+        let mut line = format!(
+            "{label} {percent}% {}/{}",
+            format_size(done),
+            format_size(total)
+        );
+        if let Some(rate) = self.rate(now) {
+            line.push_str(&format!(" {}/s", format_size(rate as u64)));
+            if rate > 0.0 && total > done {
+                let eta = Duration::from_secs_f64((total - done) as f64 / rate);
+                line.push_str(&format!(" ETA {}", format_duration(eta)));
+            }
+        }
+        Some(line)
+    }
+
+    fn position(&self, path: &str) -> Option<usize> {
+        self.active.iter().position(|file| file.path == path)
+    }
 }
 
-impl ConsoleProgressReporter {
-    pub fn new(label: impl Into<String>, total_bytes: Option<u64>, step_bytes: u64) -> Self {
+fn percent(done: u64, total: u64) -> u64 {
+    if total == 0 {
+        100
+    } else {
+        (done.min(total) as f64 / total as f64 * 100.0) as u64
+    }
+}
+
+/// Format a duration as `45s`, `3m07s` or `1h02m`.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Label of a file being transferred by `action`.
+fn verb(action: Action) -> &'static str {
+    match action {
+        Action::Upload => "Uploading",
+        Action::Download => "Downloading",
+        Action::Copy => "Copying",
+        Action::Move => "Moving",
+        Action::Sync => "Syncing",
+        Action::Mkdir | Action::Delete => "Processing",
+    }
+}
+
+impl ConsoleProgress {
+    /// Create a console progress, drawn only when stderr is a terminal.
+    pub fn new() -> Self {
         Self {
-            label: label.into(),
-            total_bytes,
-            step_bytes: step_bytes.max(1),
+            enabled: io::stderr().is_terminal(),
+            state: Mutex::new(JobState::new(0, 0)),
         }
     }
 
-    /// Print progress if a reporting threshold has been reached.
-    pub fn maybe_report(&self, processed_bytes: u64) {
-        if let Some(total) = self.total_bytes {
-            if total == 0 {
+    fn maybe_draw(&self, state: &mut JobState) {
+        let now = Instant::now();
+        if state
+            .last_draw
+            .is_some_and(|last| now.duration_since(last) < REFRESH_INTERVAL)
+        {
+            return;
+        }
+        if let Some(line) = state.line(now) {
+            draw_line(&line);
+            state.last_draw = Some(now);
+        }
+    }
+}
+
+impl Default for ConsoleProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressObserver for ConsoleProgress {
+    fn on_event(&self, event: ProgressEvent<'_>) {
+        if !self.enabled {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        match event {
+            ProgressEvent::JobStarted { files, bytes } => *state = JobState::new(files, bytes),
+            ProgressEvent::FileStarted { action, path, size } => {
+                state.active.push(ActiveFile {
+                    path: path.to_string(),
+                    label: format!("{} {path}", verb(action)),
+                    size,
+                    done: 0,
+                });
+            }
+            ProgressEvent::BytesTransferred { path, bytes } => {
+                if let Some(index) = state.position(path) {
+                    state.active[index].done = bytes;
+                }
+            }
+            ProgressEvent::FileFinished { path, bytes } => {
+                if let Some(index) = state.position(path) {
+                    let file = state.active.remove(index);
+                    state.files_done += 1;
+                    state.bytes_finished += bytes.max(file.size);
+                }
+            }
+            ProgressEvent::FileFailed { path, .. } => {
+                if let Some(index) = state.position(path) {
+                    state.active.remove(index);
+                }
+            }
+            // Notes are printed by other observers; the line is redrawn after them.
+            ProgressEvent::FileResumed { .. } | ProgressEvent::Warning { .. } => {
+                clear_progress_line();
                 return;
             }
-            if processed_bytes.is_multiple_of(self.step_bytes) {
-                let progress = ((processed_bytes as f64 / total as f64) * 100.0) as u32;
-                print!("\r {}: {}%", self.label, progress);
-                let _ = io::stdout().flush();
+            ProgressEvent::JobFinished => {
+                clear_progress_line();
+                return;
             }
         }
+        self.maybe_draw(&mut state);
     }
 }
@@ -0,0 +1,319 @@
+// Typed per-item results of storage operations
+use crate::error::{Error, Result};
+use serde::Serialize;
+use std::fmt;
+use std::sync::Arc;
+
+/// Kind of change an item of an operation is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Upload,
+    Download,
+    Copy,
+    Move,
+    Sync,
+    Mkdir,
+    Delete,
+}
+
+/// Outcome of a single item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemStatus {
+    /// The change was made.
+    Ok,
+    /// The change would be made, but this is a dry run.
+    Planned,
+    /// Nothing to do: the target already exists.
+    Exists,
+    /// The item was left alone, e.g. a non-empty directory.
+    Skipped,
+    /// The item does not exist.
+    Missing,
+    /// The change failed; see `error`.
+    Failed,
+}
+
+/// How a file reached its destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TransferMethod {
+    /// Streamed through the client.
+    Stream,
+    /// Copied by the storage service.
+    ServerSideCopy,
+    /// Renamed by the storage service.
+    ServerSideRename,
+}
+
+/// Result of transferring one file (`put`, `get`, `cp`, `mv`, `sync`).
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferRecord {
+    pub action: Action,
+    pub status: ItemStatus,
+    pub src: String,
+    pub dest: String,
+    /// Bytes transferred; unknown for server-side operations.
+    pub bytes: Option<u64>,
+    pub method: Option<TransferMethod>,
+    pub error: Option<String>,
+}
+
+impl TransferRecord {
+    /// A completed transfer.
+    pub fn done(
+        action: Action,
+        src: &str,
+        dest: &str,
+        bytes: Option<u64>,
+        method: TransferMethod,
+    ) -> Self {
+        Self {
+            action,
+            status: ItemStatus::Ok,
+            src: src.to_string(),
+            dest: dest.to_string(),
+            bytes,
+            method: Some(method),
+            error: None,
+        }
+    }
+
+    /// A transfer a dry run would make.
+    pub fn planned(action: Action, src: &str, dest: &str, bytes: u64) -> Self {
+        Self {
+            action,
+            status: ItemStatus::Planned,
+            src: src.to_string(),
+            dest: dest.to_string(),
+            bytes: Some(bytes),
+            method: None,
+            error: None,
+        }
+    }
+
+    /// A file that was left alone, e.g. because it vanished after being listed.
+    pub fn skipped(action: Action, src: &str, dest: &str) -> Self {
+        Self {
+            action,
+            status: ItemStatus::Skipped,
+            src: src.to_string(),
+            dest: dest.to_string(),
+            bytes: None,
+            method: None,
+            error: None,
+        }
+    }
+
+    /// A failed transfer.
+    pub fn failed(action: Action, src: &str, dest: &str, error: impl fmt::Display) -> Self {
+        Self {
+            action,
+            status: ItemStatus::Failed,
+            src: src.to_string(),
+            dest: dest.to_string(),
+            bytes: None,
+            method: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// Result of deleting or creating one path (`rm`, `mkdir`).
+#[derive(Debug, Clone, Serialize)]
+pub struct PathRecord {
+    pub action: Action,
+    pub status: ItemStatus,
+    pub path: String,
+    pub error: Option<String>,
+}
+
+impl PathRecord {
+    pub fn new(action: Action, status: ItemStatus, path: &str) -> Self {
+        Self {
+            action,
+            status,
+            path: path.to_string(),
+            error: None,
+        }
+    }
+
+    /// A change that failed with `error`.
+    pub fn failed(action: Action, path: &str, error: impl fmt::Display) -> Self {
+        Self {
+            error: Some(error.to_string()),
+            ..Self::new(action, ItemStatus::Failed, path)
+        }
+    }
+}
+
+/// A path an operation failed on, as collected by `Error::PartialTransfer` and
+/// `Error::PartialDeletion`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Failure {
+    pub action: Action,
+    pub path: String,
+    /// Short name of what went wrong, e.g. `NotFound` or `PermissionDenied`
+    pub kind: String,
+    pub error: String,
+}
+
+impl Failure {
+    pub fn new(action: Action, path: &str, kind: &str, error: impl fmt::Display) -> Self {
+        Self {
+            action,
+            path: path.to_string(),
+            kind: kind.to_string(),
+            error: error.to_string(),
+        }
+    }
+
+    /// A failure caused by `error`, whose `Error::kind` names it.
+    pub fn from_error(action: Action, path: &str, error: &Error) -> Self {
+        Self::new(action, path, &error.kind(), error)
+    }
+}
+
+/// Files handled by a transfer operation, in completion order.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TransferReport {
+    pub items: Vec<TransferRecord>,
+}
+
+impl TransferReport {
+    /// Total number of bytes of the transferred (or planned) files, where known.
+    pub fn bytes(&self) -> u64 {
+        self.items.iter().filter_map(|item| item.bytes).sum()
+    }
+}
+
+/// An entry listed by `ls`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListEntry {
+    pub path: String,
+    /// `dir` or `file`
+    #[serde(rename = "type")]
+    pub entry_type: &'static str,
+    pub size: u64,
+    /// Last modification time (RFC 3339), if the backend reports one
+    pub last_modified: Option<String>,
+}
+
+impl ListEntry {
+    pub fn from_entry(entry: &opendal::Entry) -> Self {
+        let meta = entry.metadata();
+        Self {
+            path: entry.path().to_string(),
+            entry_type: if meta.mode().is_dir() { "dir" } else { "file" },
+            size: meta.content_length(),
+            last_modified: meta.last_modified().map(|t| t.to_rfc3339()),
+        }
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.entry_type == "dir"
+    }
+}
+
+/// Disk usage of an entry (`files` is 1) or the total under a path (`du`).
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageEntry {
+    pub path: String,
+    pub size: u64,
+    pub files: u64,
+}
+
+/// Content checksum of a file (`checksum`).
+#[derive(Debug, Clone, Serialize)]
+pub struct ChecksumEntry {
+    /// Path of the file; relative to the directory prefix in manifests
+    pub path: String,
+    pub hash: String,
+    /// ETag reported by the backend, without quotes
+    pub etag: Option<String>,
+}
+
+/// A per-item result, as delivered to an `ItemObserver`.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(untagged)]
+pub enum Item<'a> {
+    Transfer(&'a TransferRecord),
+    Path(&'a PathRecord),
+    Entry(&'a ListEntry),
+    Usage(&'a UsageEntry),
+    Checksum(&'a ChecksumEntry),
+}
+
+/// Receives the result of every item as soon as an operation has handled it.
+///
+/// Operations also return their results once they complete; observers let callers
+/// follow long recursive jobs while they run.
+pub trait ItemObserver: Send + Sync {
+    fn on_item(&self, item: Item<'_>);
+}
+
+/// Handle through which operations deliver items to an optional `ItemObserver`.
+#[derive(Clone, Default)]
+pub struct Reporter {
+    observer: Option<Arc<dyn ItemObserver>>,
+}
+
+impl fmt::Debug for Reporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reporter")
+            .field("observed", &self.observer.is_some())
+            .finish()
+    }
+}
+
+impl Reporter {
+    pub fn new(observer: Arc<dyn ItemObserver>) -> Self {
+        Self {
+            observer: Some(observer),
+        }
+    }
+
+    pub fn report(&self, item: Item<'_>) {
+        if let Some(observer) = &self.observer {
+            observer.on_item(item);
+        }
+    }
+
+    pub fn transfer(&self, record: &TransferRecord) {
+        self.report(Item::Transfer(record));
+    }
+
+    pub fn path(&self, record: &PathRecord) {
+        self.report(Item::Path(record));
+    }
+
+    /// Report `result` as a failed transfer of `src` when the operation failed as a whole,
+    /// e.g. because the source of `get` or `cp` does not exist.
+    ///
+    /// Failures of single files (`Error::failures`) have been reported already.
+    pub fn transfer_result<T>(
+        &self,
+        result: Result<T>,
+        action: Action,
+        src: &str,
+        dest: &str,
+    ) -> Result<T> {
+        if let Err(e) = &result
+            && e.failures().is_none()
+        {
+            self.transfer(&TransferRecord::failed(action, src, dest, e));
+        }
+        result
+    }
+
+    /// Report `result` as a failed change of `path` when the operation failed as a whole.
+    pub fn path_result<T>(&self, result: Result<T>, action: Action, path: &str) -> Result<T> {
+        if let Err(e) = &result
+            && e.failures().is_none()
+        {
+            self.path(&PathRecord::failed(action, path, e));
+        }
+        result
+    }
+}
@@ -0,0 +1,114 @@
+// Recorded state that lets interrupted transfers continue where they stopped
+use crate::error::Result;
+use crate::storage::constants::{CONFIG_DIR_NAME, RESUME_DIR_NAME};
+use crate::storage::utils::checksum::hex;
+use chrono::{DateTime, Utc};
+use opendal::Metadata;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Suffix of the temporary sibling a download is written to before it is renamed into place.
+const PARTIAL_SUFFIX: &str = ".storify-part";
+
+/// Identity of a transfer's source, recorded when the transfer starts.
+///
+/// A partial transfer is only continued when the recorded state still matches the source,
+/// so that a file changed in the meantime is never stitched together from two versions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResumeState {
+    /// Remote path the transfer reads from or writes to
+    pub remote_path: String,
+    /// Size of the source in bytes
+    pub size: u64,
+    /// ETag of the source, when known
+    pub etag: Option<String>,
+    /// Last modification time of the source (RFC 3339), when known
+    pub last_modified: Option<String>,
+}
+
+impl ResumeState {
+    /// State of a remote object about to be downloaded.
+    pub fn from_remote(remote_path: &str, meta: &Metadata) -> Self {
+        Self {
+            remote_path: remote_path.to_string(),
+            size: meta.content_length(),
+            etag: meta.etag().map(str::to_string),
+            last_modified: meta.last_modified().map(|t| t.to_rfc3339()),
+        }
+    }
+
+    /// State of a local file about to be uploaded to `remote_path`.
+    pub fn from_local(remote_path: &str, meta: &std::fs::Metadata) -> Self {
+        Self {
+            remote_path: remote_path.to_string(),
+            size: meta.len(),
+            etag: None,
+            last_modified: meta
+                .modified()
+                .ok()
+                .map(|t| DateTime::<Utc>::from(t).to_rfc3339()),
+        }
+    }
+}
+
+/// Directory holding the state of interrupted transfers:
+/// `$XDG_STATE_HOME/storify/resume`, falling back to `$HOME/.local/state/storify/resume`
+/// and then to the temporary directory.
+fn state_dir() -> PathBuf {
+    let base = env::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")))
+        .unwrap_or_else(env::temp_dir);
+    base.join(CONFIG_DIR_NAME).join(RESUME_DIR_NAME)
+}
+
+/// Path of the state file belonging to `local_path`, named after the SHA-256 of its
+/// absolute path so that state never lands in the tree being transferred.
+pub fn state_path(local_path: &Path) -> PathBuf {
+    let absolute = std::path::absolute(local_path).unwrap_or_else(|_| local_path.to_path_buf());
+    let digest = Sha256::digest(absolute.as_os_str().as_encoded_bytes());
+    state_dir().join(format!("{}.json", hex(&digest)))
+}
+
+/// Path of the partial file written while `local_path` is being downloaded.
+pub fn partial_path(local_path: &Path) -> PathBuf {
+    with_suffix(local_path, PARTIAL_SUFFIX)
+}
+
+/// Whether `path` is a partial file left behind by an interrupted download.
+pub fn is_partial(path: &Path) -> bool {
+    path.as_os_str().to_string_lossy().ends_with(PARTIAL_SUFFIX)
+}
+
+fn with_suffix(local_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = local_path.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Load the state recorded for `local_path`, if any.
+///
+/// Unreadable or malformed state is treated as absent.
+pub async fn load(local_path: &Path) -> Option<ResumeState> {
+    let content = fs::read(state_path(local_path)).await.ok()?;
+    serde_json::from_slice(&content).ok()
+}
+
+/// Record `state` for `local_path`.
+pub async fn save(local_path: &Path, state: &ResumeState) -> Result<()> {
+    let path = state_path(local_path);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).await?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(state)?).await?;
+    Ok(())
+}
+
+/// Remove the state recorded for `local_path` once its transfer has completed.
+pub async fn clear(local_path: &Path) {
+    let _ = fs::remove_file(state_path(local_path)).await;
+}
@@ -13,3 +13,24 @@ pub fn format_size(size: u64) -> String {
     }
     format!("{size_f:.1}{}", UNITS[unit_index])
 }
+
+/// Parse a human-readable size such as `512`, `64K`, `8M`, `8MiB` or `1G` into bytes.
+///
+/// Units use the same 1024 base as [`format_size`] and are case-insensitive.
+pub fn parse_size(input: &str) -> Option<u64> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    let number: u64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1024,
+        "M" | "MB" | "MIB" => 1024 * 1024,
+        "G" | "GB" | "GIB" => 1024 * 1024 * 1024,
+        "T" | "TB" | "TIB" => 1024 * 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    number.checked_mul(multiplier)
+}
@@ -0,0 +1,60 @@
+// Bandwidth limiting shared by concurrent transfers
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Token bucket capping the combined throughput of every transfer holding a clone.
+///
+/// The bucket holds at most one second worth of bytes, so short bursts pass at full
+/// speed while the long-term rate never exceeds the limit. Transfers take the bytes
+/// they just moved from the bucket; when it runs dry, they wait until it has refilled
+/// enough to cover their debt.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bytes_per_sec: u64,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available bytes; negative when transfers are ahead of the limit
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing `bytes_per_sec` bytes per second (at least one).
+    pub fn new(bytes_per_sec: u64) -> Self {
+        let bytes_per_sec = bytes_per_sec.max(1);
+        Self {
+            bytes_per_sec,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: bytes_per_sec as f64,
+                refilled: Instant::now(),
+            })),
+        }
+    }
+
+    /// Account for `bytes` transferred, waiting as long as needed to stay under the limit.
+    pub async fn consume(&self, bytes: u64) {
+        let wait = {
+            let rate = self.bytes_per_sec as f64;
+            let mut bucket = self.bucket.lock().unwrap();
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.refilled).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * rate).min(rate);
+            bucket.refilled = now;
+            bucket.tokens -= bytes as f64;
+            (bucket.tokens < 0.0).then(|| Duration::from_secs_f64(-bucket.tokens / rate))
+        };
+        if let Some(wait) = wait {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Account for `bytes` transferred on an optional limiter.
+pub async fn throttle(limiter: Option<&RateLimiter>, bytes: u64) {
+    if let Some(limiter) = limiter {
+        limiter.consume(bytes).await;
+    }
+}
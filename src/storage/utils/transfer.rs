@@ -0,0 +1,299 @@
+// Transfer helpers shared by copy, move and related operations
+use crate::error::{PartialTransferSnafu, Result};
+use crate::storage::constants::{
+    DEFAULT_CHUNK_SIZE, MAX_COPY_OBJECT_SIZE, MAX_OSS_COPY_OBJECT_SIZE,
+};
+use crate::storage::utils::backend::Backend;
+use crate::storage::utils::checksum::{
+    Checksum, ChecksumAlgo, TransferHasher, advertised_checksum, compute_checksum, ensure_match,
+    verify_object,
+};
+use crate::storage::utils::options::TransferOptions;
+use crate::storage::utils::path::ensure_trailing_slash;
+use crate::storage::utils::progress::clear_progress_line;
+use crate::storage::utils::report::{Action, Failure, TransferRecord, TransferReport};
+use crate::storage::utils::throttle::throttle;
+use futures::stream::{self, StreamExt};
+use opendal::{EntryMode, ErrorKind, Operator, Scheme};
+use std::future::Future;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A single file transfer scheduled by a recursive operation.
+#[derive(Debug, Clone)]
+pub struct TransferTask {
+    /// Source path (local or remote, depending on the operation)
+    pub src: String,
+    /// Destination path (local or remote, depending on the operation)
+    pub dest: String,
+    /// Size of the source as listed, used to plan progress; 0 when unknown
+    pub size: u64,
+}
+
+impl TransferTask {
+    pub fn new(src: impl Into<String>, dest: impl Into<String>, size: u64) -> Self {
+        Self {
+            src: src.into(),
+            dest: dest.into(),
+            size,
+        }
+    }
+}
+
+/// Run file transfers with at most `options.jobs` of them in flight.
+///
+/// The transfers form one job of `options.progress`, started with the listed sizes of all
+/// tasks, so recursive jobs can show their aggregate progress. Every result, including
+/// failures as a failed `action`, is passed to `options.reporter` as soon as it is known.
+/// After the first failure no new transfers are started, unless
+/// `options.continue_on_error` is set, while those already running are allowed to
+/// finish; the failures are then returned together as `Error::PartialTransfer`.
+pub async fn run_transfers<F, Fut>(
+    tasks: Vec<TransferTask>,
+    action: Action,
+    options: &TransferOptions,
+    transfer: F,
+) -> Result<TransferReport>
+where
+    F: Fn(TransferTask) -> Fut,
+    Fut: Future<Output = Result<TransferRecord>>,
+{
+    let progress = &options.progress;
+    progress.start_job(tasks.len(), tasks.iter().map(|task| task.size).sum());
+    let aborted = AtomicBool::new(false);
+    let failures = Mutex::new(Vec::new());
+    let report = Mutex::new(TransferReport::default());
+
+    stream::iter(tasks)
+        .map(|task| {
+            let aborted = &aborted;
+            let failures = &failures;
+            let report = &report;
+            let transfer = &transfer;
+            async move {
+                if aborted.load(Ordering::Relaxed) {
+                    return;
+                }
+                let (src, dest) = (task.src.clone(), task.dest.clone());
+                let record = match transfer(task).await {
+                    Ok(record) => record,
+                    Err(e) => {
+                        progress.file_failed(&src, &e);
+                        if !options.continue_on_error {
+                            aborted.store(true, Ordering::Relaxed);
+                        }
+                        let failure = Failure::from_error(action, &src, &e);
+                        failures.lock().unwrap().push(failure);
+                        TransferRecord::failed(action, &src, &dest, e)
+                    }
+                };
+                clear_progress_line();
+                options.reporter.transfer(&record);
+                report.lock().unwrap().items.push(record);
+            }
+        })
+        .buffer_unordered(options.jobs.max(1))
+        .collect::<()>()
+        .await;
+    progress.finish_job();
+
+    let failures = failures.into_inner().unwrap();
+    if !failures.is_empty() {
+        return PartialTransferSnafu { failures }.fail();
+    }
+    Ok(report.into_inner().unwrap())
+}
+
+/// Run the transfer of the single file `src` as a job of its own and report the result.
+pub async fn run_transfer(
+    options: &TransferOptions,
+    src: &str,
+    transfer: impl Future<Output = Result<TransferRecord>>,
+) -> Result<TransferReport> {
+    let progress = &options.progress;
+    progress.start_job(1, 0);
+    let result = transfer.await;
+    if let Err(e) = &result {
+        progress.file_failed(src, e);
+    }
+    progress.finish_job();
+    let record = result?;
+    options.reporter.transfer(&record);
+    Ok(TransferReport {
+        items: vec![record],
+    })
+}
+
+/// Whether two backends address the same storage: same service, endpoint, region,
+/// bucket (name) and root.
+///
+/// Only then can paths from one be handed to server-side operations of the other.
+pub fn same_storage(a: &Backend, b: &Backend) -> bool {
+    let (a_info, b_info) = (a.operator.info(), b.operator.info());
+    a_info.scheme() == b_info.scheme()
+        && a_info.name() == b_info.name()
+        && a_info.root() == b_info.root()
+        && a.endpoint == b.endpoint
+        && a.region == b.region
+}
+
+/// Copy a file within one storage without streaming it through the client.
+///
+/// Nothing is copied when the service cannot copy the file itself: the object is larger
+/// than the service copies in a single request, or the backend turns out not to support
+/// copies. The caller then streams the file instead.
+///
+/// With `verify`, the checksum of the copy is compared against the source's.
+///
+/// # Returns
+/// * `Result<bool>` - Whether the file was copied
+pub async fn server_side_copy(
+    backend: &Backend,
+    src_path: &str,
+    dest_path: &str,
+    verify: bool,
+) -> Result<bool> {
+    let operator = &backend.operator;
+    if let Some(limit) = max_copy_size(operator.info().scheme()) {
+        let size = operator.stat(src_path).await?.content_length();
+        if size > limit {
+            log::debug!("{src_path} ({size} bytes) exceeds the server-side copy limit; streaming");
+            return Ok(false);
+        }
+    }
+    match backend.server_side.copy(src_path, dest_path).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::Unsupported => {
+            log::debug!("server-side copy of {src_path} unsupported; streaming: {e}");
+            return Ok(false);
+        }
+        Err(e) => return Err(e.into()),
+    }
+    if verify {
+        // Equal advertised checksums prove the copy intact; otherwise the source is read
+        // back, since an advertised ETag need not be an MD5.
+        if let Some(expected) = advertised_checksum(operator, src_path).await?
+            && advertised_checksum(operator, dest_path).await? == Some(expected)
+        {
+            return Ok(true);
+        }
+        let expected = Checksum {
+            algo: ChecksumAlgo::Md5,
+            value: compute_checksum(operator, src_path, ChecksumAlgo::Md5).await?,
+        };
+        verify_object(operator, dest_path, &[expected]).await?;
+    }
+    Ok(true)
+}
+
+/// Largest object a service copies in a single request, if it has a limit.
+fn max_copy_size(scheme: Scheme) -> Option<u64> {
+    match scheme {
+        Scheme::S3 | Scheme::Cos => Some(MAX_COPY_OBJECT_SIZE),
+        Scheme::Oss => Some(MAX_OSS_COPY_OBJECT_SIZE),
+        _ => None,
+    }
+}
+
+/// Hybrid directory detection for object storage: stat first; if not available, probe prefix.
+pub async fn is_directory(operator: &Operator, path: &str) -> bool {
+    match operator.stat(path).await.ok().map(|m| m.mode()) {
+        Some(EntryMode::DIR) => true,
+        Some(_) => false,
+        None => {
+            let probe = ensure_trailing_slash(path);
+            operator
+                .list_with(&probe)
+                .limit(1)
+                .await
+                .map(|entries| !entries.is_empty())
+                .unwrap_or(false)
+        }
+    }
+}
+
+/// Ensure a remote directory exists (appends trailing '/').
+///
+/// On a dry run the directory is only recorded in the plan.
+pub async fn ensure_directory(
+    operator: &Operator,
+    dir_path: &str,
+    options: &TransferOptions,
+) -> Result<()> {
+    let to_create = ensure_trailing_slash(dir_path);
+    match &options.dry_run {
+        Some(plan) => options.reporter.path(&plan.mkdir(&to_create)),
+        None => operator.create_dir(&to_create).await?,
+    }
+    Ok(())
+}
+
+/// Stream a single file from one operator to another in chunks.
+///
+/// Source and destination may be the same operator or belong to different providers.
+/// The file is reported to `options.progress` as transferred by `action`, and its
+/// bytes count against `options.rate_limit`. With `verify`, the digests of the
+/// streamed bytes are checked against the source's advertised checksum (when available,
+/// reading the source back if it differs) and against the stored destination object.
+///
+/// # Returns
+/// * `Result<u64>` - Number of bytes transferred
+pub async fn stream_copy(
+    src: &Backend,
+    src_path: &str,
+    dest: &Backend,
+    dest_path: &str,
+    action: Action,
+    options: &TransferOptions,
+    verify: bool,
+) -> Result<u64> {
+    let (src_operator, dest_operator) = (&src.operator, &dest.operator);
+    let metadata = src_operator.stat(src_path).await?;
+    let file_size = metadata.content_length();
+
+    let mut writer = dest_operator.writer(dest_path).await?;
+    let mut total_bytes = 0u64;
+    let mut offset = 0u64;
+    let mut hasher = verify.then(TransferHasher::default);
+
+    let file_progress = options.progress.start_file(action, src_path, file_size);
+
+    while offset < file_size {
+        let chunk_size = std::cmp::min(DEFAULT_CHUNK_SIZE as u64, file_size - offset);
+
+        let data = src_operator
+            .read_with(src_path)
+            .range(offset..offset + chunk_size)
+            .await?;
+        let data_len = data.len();
+        if data_len == 0 {
+            break;
+        }
+
+        if let Some(hasher) = &mut hasher {
+            for chunk in data.clone() {
+                hasher.update(&chunk);
+            }
+        }
+        writer.write(data).await?;
+        throttle(options.rate_limit.as_ref(), data_len as u64).await;
+        total_bytes += data_len as u64;
+        offset += data_len as u64;
+
+        file_progress.set(total_bytes);
+    }
+
+    writer.close().await?;
+    file_progress.finish(total_bytes);
+
+    if let Some(hasher) = hasher {
+        let digests = hasher.finish();
+        if let Some(stored) = advertised_checksum(src_operator, src_path).await?
+            && ensure_match(src_path, &stored, &digests).is_err()
+        {
+            verify_object(src_operator, src_path, &digests).await?;
+        }
+        verify_object(dest_operator, dest_path, &digests).await?;
+    }
+    Ok(total_bytes)
+}
@@ -0,0 +1,174 @@
+// Recursive tree walking shared by sync-style operations
+use crate::error::Result;
+use crate::storage::operations::stat::{ObjectMeta, OpenDalStater, Stater};
+use crate::storage::utils::filter::PathFilter;
+use crate::storage::utils::path::ensure_trailing_slash;
+use futures::stream::TryStreamExt;
+use opendal::{EntryMode, ErrorKind, Operator};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// A regular file found while walking a local directory tree.
+#[derive(Debug, Clone)]
+pub struct LocalFile {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// Why a file present in two trees is considered changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeReason {
+    Size,
+    Etag,
+    Mtime,
+}
+
+impl fmt::Display for ChangeReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Size => "size",
+            Self::Etag => "etag",
+            Self::Mtime => "mtime",
+        };
+        f.write_str(name)
+    }
+}
+
+/// ETags are only comparable when both sides are served by the same kind of backend.
+pub fn etags_comparable(a: &Operator, b: &Operator) -> bool {
+    a.info().scheme() == b.info().scheme()
+}
+
+/// Compare a source file with its counterpart in another tree.
+///
+/// The file has changed when the sizes differ, the ETags differ (when comparable), or
+/// the modification times differ. With `symmetric` unset only a source modified after
+/// the counterpart counts, which is what sync needs; diff sets it to report any
+/// difference, but only between trees on the same kind of backend (`compare_etag`):
+/// across backends the times record when a copy was made, not when its content changed.
+/// Modification times are compared in whole seconds, the precision of HTTP
+/// `Last-Modified`.
+pub fn change_reason(
+    src: &ObjectMeta,
+    dest: &ObjectMeta,
+    compare_etag: bool,
+    symmetric: bool,
+) -> Option<ChangeReason> {
+    if src.size != dest.size {
+        return Some(ChangeReason::Size);
+    }
+    if compare_etag && let (Some(a), Some(b)) = (&src.etag, &dest.etag) {
+        return (a != b).then_some(ChangeReason::Etag);
+    }
+    match (src.last_modified, dest.last_modified) {
+        _ if symmetric && !compare_etag => None,
+        (Some(src_time), Some(dest_time)) => {
+            let (src_secs, dest_secs) = (src_time.timestamp(), dest_time.timestamp());
+            let changed = if symmetric {
+                src_secs != dest_secs
+            } else {
+                src_secs > dest_secs
+            };
+            changed.then_some(ChangeReason::Mtime)
+        }
+        _ => None,
+    }
+}
+
+/// Normalize a directory root into the prefix its listed entries start with.
+///
+/// `"/"` and `""` map to the empty prefix; anything else gains a trailing slash.
+pub fn dir_prefix(root: &str) -> String {
+    let trimmed = root.trim_start_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        ensure_trailing_slash(trimmed)
+    }
+}
+
+/// Recursively collect the files under `root` selected by `filter`, keyed by their path
+/// relative to `root`.
+///
+/// Listing metadata is used when it is complete. Backends that list without sizes or
+/// timestamps (such as the local `fs` service) are filled in through `OpenDalStater`.
+/// A missing root yields an empty map so that a fresh destination can be synced into.
+pub async fn collect_files(
+    operator: &Operator,
+    root: &str,
+    filter: &PathFilter,
+) -> Result<BTreeMap<String, ObjectMeta>> {
+    let prefix = dir_prefix(root);
+    let list_root = if prefix.is_empty() { "/" } else { &prefix };
+    let stater = OpenDalStater::new(operator.clone());
+    let mut files = BTreeMap::new();
+
+    let mut lister = match operator.lister_with(list_root).recursive(true).await {
+        Ok(lister) => lister,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(files),
+        Err(e) => return Err(e.into()),
+    };
+
+    while let Some(entry) = lister.try_next().await? {
+        let meta = entry.metadata();
+        if meta.mode() == EntryMode::DIR {
+            continue;
+        }
+
+        let path = entry.path();
+        let relative = path
+            .trim_start_matches('/')
+            .strip_prefix(prefix.as_str())
+            .unwrap_or(path)
+            .to_string();
+        if relative.is_empty() || !filter.matches(&relative) {
+            continue;
+        }
+
+        let object = if meta.last_modified().is_some() {
+            ObjectMeta::from_metadata(path, meta)
+        } else {
+            stater.stat(path).await?
+        };
+        files.insert(relative, object);
+    }
+
+    Ok(files)
+}
+
+/// Recursively collect the regular files under a local directory selected by `filter`,
+/// keyed by their `/`-separated path relative to `root`.
+pub async fn collect_local_files(
+    root: &Path,
+    filter: &PathFilter,
+) -> Result<BTreeMap<String, LocalFile>> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![(root.to_path_buf(), String::new())];
+
+    while let Some((dir, prefix)) = pending.pop() {
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let relative = format!("{prefix}{name}");
+            let file_type = entry.file_type().await?;
+            if file_type.is_dir() {
+                pending.push((entry.path(), format!("{relative}/")));
+            } else if file_type.is_file() && filter.matches(&relative) {
+                let size = entry.metadata().await?.len();
+                files.insert(
+                    relative,
+                    LocalFile {
+                        path: entry.path(),
+                        size,
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(files)
+}
@@ -1,6 +1,6 @@
 /// Utility functions for user interaction and common operations.
 use crate::error::Result;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 
 /// Prompt user for confirmation before performing potentially destructive operations.
 pub fn confirm_deletion(paths: &[String], force: bool) -> Result<bool> {
@@ -25,3 +25,29 @@ pub fn confirm_deletion(paths: &[String], force: bool) -> Result<bool> {
     let trimmed = input.trim().to_lowercase();
     Ok(trimmed == "y" || trimmed == "yes")
 }
+
+/// Ask whether to display a file larger than the configured size limit.
+///
+/// Without an interactive stdin the answer is no.
+///
+/// # Arguments
+/// * `file_size_mb` - The file's size in MB
+/// * `limit_mb` - The size limit in MB that triggers confirmation
+///
+/// # Returns
+/// * `Result<bool>` - `Ok(true)` to continue, `Ok(false)` to abort; error on I/O failures
+pub fn confirm_large_file(file_size_mb: u64, limit_mb: u64) -> Result<bool> {
+    if !io::stdin().is_terminal() {
+        eprintln!("File too large ({file_size_mb}MB > {limit_mb}MB). Use force to override.");
+        return Ok(false);
+    }
+
+    eprint!("File too large ({file_size_mb}MB > {limit_mb}MB). Continue? [y/N]: ");
+    io::stderr().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    let ans = input.trim();
+    Ok(ans.eq_ignore_ascii_case("y") || ans.eq_ignore_ascii_case("yes"))
+}
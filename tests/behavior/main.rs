@@ -24,6 +24,21 @@ fn main() -> Result<()> {
     operations::cat::tests(&client, &mut tests);
     operations::usage::tests(&client, &mut tests);
     operations::stat::tests(&client, &mut tests);
+    operations::config::tests(&client, &mut tests);
+    operations::location::tests(&client, &mut tests);
+    operations::sync::tests(&client, &mut tests);
+    operations::checksum::tests(&client, &mut tests);
+    operations::verify::tests(&client, &mut tests);
+    operations::diff::tests(&client, &mut tests);
+    operations::filter::tests(&client, &mut tests);
+    operations::glob::tests(&client, &mut tests);
+    operations::find::tests(&client, &mut tests);
+    operations::dry_run::tests(&client, &mut tests);
+    operations::output::tests(&client, &mut tests);
+    operations::progress::tests(&client, &mut tests);
+    operations::retry::tests(&client, &mut tests);
+    operations::throttle::tests(&client, &mut tests);
+    operations::continue_on_error::tests(&client, &mut tests);
 
     let _ = tracing_subscriber::fmt()
         .pretty()
@@ -0,0 +1,89 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use sha2::{Digest, Sha256};
+use storify::error::Result;
+use storify::storage::StorageClient;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_checksum_file_algorithms,
+        test_checksum_manifest_for_prefix,
+        test_checksum_non_existent_path
+    ));
+}
+
+async fn test_checksum_file_algorithms(client: StorageClient) -> Result<()> {
+    let path = format!("{}check.txt", TEST_FIXTURE.new_dir_path());
+    client
+        .operator()
+        .write(&path, b"123456789".to_vec())
+        .await?;
+
+    for (algo, expected) in [
+        ("md5", "25f9e794323b453885f5181f1b624d0b"),
+        (
+            "sha256",
+            "15e2b0d3c33891ebb0f1ef609ec419420c20e320ce94c65fbc8c3312448eb225",
+        ),
+        ("crc32c", "e3069283"),
+    ] {
+        storify_cmd()
+            .arg("checksum")
+            .arg("--algo")
+            .arg(algo)
+            .arg(&path)
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with(format!("{expected}  ")))
+            .stdout(predicate::str::contains(&path));
+    }
+
+    Ok(())
+}
+
+async fn test_checksum_manifest_for_prefix(client: StorageClient) -> Result<()> {
+    let dir = TEST_FIXTURE.new_dir_path();
+    client
+        .operator()
+        .write(&format!("{dir}a.txt"), b"alpha".to_vec())
+        .await?;
+    client
+        .operator()
+        .write(&format!("{dir}nested/b.txt"), b"beta".to_vec())
+        .await?;
+
+    let sha = |data: &[u8]| {
+        Sha256::digest(data)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>()
+    };
+    let expected = format!("{}  a.txt\n{}  nested/b.txt\n", sha(b"alpha"), sha(b"beta"));
+
+    storify_cmd()
+        .arg("checksum")
+        .arg("--algo")
+        .arg("sha256")
+        .arg("--manifest")
+        .arg(&dir)
+        .assert()
+        .success()
+        .stdout(predicate::eq(expected));
+
+    Ok(())
+}
+
+async fn test_checksum_non_existent_path(_client: StorageClient) -> Result<()> {
+    let path = TEST_FIXTURE.new_file_path();
+
+    storify_cmd()
+        .arg("checksum")
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to checksum"));
+
+    Ok(())
+}
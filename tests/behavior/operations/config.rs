@@ -0,0 +1,135 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::PathBuf;
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_profile_selected_by_flag,
+        test_default_profile_from_config_file,
+        test_env_overrides_default_profile,
+        test_selected_profile_overrides_env,
+        test_unknown_profile_fails
+    ));
+}
+
+/// Write a config file into a fresh temp directory and return its path.
+async fn write_config(content: &str) -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("storify-cfg-{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).await?;
+    let path = dir.join("config.toml");
+    fs::write(&path, content).await?;
+    Ok(path)
+}
+
+async fn stage_file(client: &StorageClient) -> Result<String> {
+    let (path, content, _) = TEST_FIXTURE.new_file(client.operator());
+    client.operator().write(&path, content).await?;
+    Ok(path)
+}
+
+async fn test_profile_selected_by_flag(client: StorageClient) -> Result<()> {
+    let path = stage_file(&client).await?;
+    let config = write_config(&format!("[profiles.dev]\n{}", minio_profile_toml())).await?;
+
+    bare_storify_cmd()
+        .arg("--config")
+        .arg(&config)
+        .arg("--profile")
+        .arg("dev")
+        .arg("stat")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("type=file"));
+
+    Ok(())
+}
+
+async fn test_default_profile_from_config_file(client: StorageClient) -> Result<()> {
+    let path = stage_file(&client).await?;
+    let config = write_config(&format!(
+        "default_profile = \"dev\"\n\n[profiles.dev]\n{}",
+        minio_profile_toml()
+    ))
+    .await?;
+
+    bare_storify_cmd()
+        .env("STORIFY_CONFIG", &config)
+        .arg("stat")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("type=file"));
+
+    Ok(())
+}
+
+async fn test_env_overrides_default_profile(client: StorageClient) -> Result<()> {
+    let path = stage_file(&client).await?;
+    // The profile points at a bucket that does not exist; env vars must win.
+    let config = write_config(
+        "default_profile = \"broken\"\n\n\
+         [profiles.broken]\nprovider = \"minio\"\nbucket = \"no-such-bucket\"\n",
+    )
+    .await?;
+
+    storify_cmd()
+        .arg("--config")
+        .arg(&config)
+        .arg("stat")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("type=file"));
+
+    Ok(())
+}
+
+async fn test_selected_profile_overrides_env(_client: StorageClient) -> Result<()> {
+    let root = std::env::temp_dir().join(format!("storify-local-{}", Uuid::new_v4()));
+    fs::create_dir_all(&root).await?;
+    fs::write(root.join("local-only.txt"), b"local").await?;
+    let config = write_config(&format!(
+        "[profiles.local]\nprovider = \"fs\"\nroot_path = \"{}\"\n",
+        root.display()
+    ))
+    .await?;
+
+    // The environment points at MinIO, but the profile selected on the command line wins.
+    storify_cmd()
+        .arg("--config")
+        .arg(&config)
+        .arg("--profile")
+        .arg("local")
+        .arg("ls")
+        .arg("/")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("local-only.txt"));
+
+    let _ = fs::remove_dir_all(&root).await;
+    Ok(())
+}
+
+async fn test_unknown_profile_fails(_client: StorageClient) -> Result<()> {
+    let config = write_config(&format!("[profiles.dev]\n{}", minio_profile_toml())).await?;
+
+    bare_storify_cmd()
+        .arg("--config")
+        .arg(&config)
+        .arg("--profile")
+        .arg("nope")
+        .arg("ls")
+        .arg("/")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Profile 'nope' not found"));
+
+    Ok(())
+}
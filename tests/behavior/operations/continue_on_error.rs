@@ -0,0 +1,260 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::path::Path;
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_upload_continues_after_failure,
+        test_download_continues_after_failure,
+        test_sync_continues_after_failure,
+        test_delete_failures_file,
+        test_failures_file_empty_on_success,
+        test_pattern_sources_share_one_report,
+        test_failures_file_cleared_when_command_fails
+    ));
+}
+
+async fn read_failures(path: &Path) -> Result<Vec<Value>> {
+    let json: Value = serde_json::from_slice(&fs::read(path).await?)?;
+    Ok(json.as_array().cloned().unwrap_or_default())
+}
+
+async fn test_upload_continues_after_failure(client: StorageClient) -> Result<()> {
+    let local = std::env::temp_dir().join(format!("storify-continue-{}", Uuid::new_v4()));
+    fs::create_dir_all(&local).await?;
+    fs::write(local.join("a.txt"), b"alpha").await?;
+    fs::write(local.join("b.txt"), b"beta").await?;
+    // A dangling link is listed as a file but cannot be opened.
+    fs::symlink(local.join("gone.txt"), local.join("broken.txt")).await?;
+    let failures_file = local.with_extension("json");
+    let remote = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("--continue-on-error")
+        .arg("--failures-file")
+        .arg(&failures_file)
+        .arg("put")
+        .arg("-R")
+        .arg("-j")
+        .arg("1")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1 path(s) failed:"))
+        .stderr(predicate::str::contains("broken.txt (NotFound)"));
+
+    for (name, content) in [("a.txt", "alpha"), ("b.txt", "beta")] {
+        let uploaded = client.operator().read(&format!("{remote}{name}")).await?;
+        assert_eq!(content.as_bytes(), uploaded.to_vec());
+    }
+    let failures = read_failures(&failures_file).await?;
+    assert_eq!(1, failures.len());
+    assert_eq!("upload", failures[0]["action"]);
+    assert_eq!("NotFound", failures[0]["kind"]);
+    assert!(
+        failures[0]["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("broken.txt")
+    );
+
+    let _ = fs::remove_dir_all(&local).await;
+    let _ = fs::remove_file(&failures_file).await;
+    Ok(())
+}
+
+async fn test_download_continues_after_failure(client: StorageClient) -> Result<()> {
+    let remote = TEST_FIXTURE.new_dir_path();
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        client
+            .operator()
+            .write(&format!("{remote}{name}"), name.as_bytes().to_vec())
+            .await?;
+    }
+    let local = std::env::temp_dir().join(format!("storify-continue-{}", Uuid::new_v4()));
+    // A directory in the way of a downloaded file makes that file fail.
+    fs::create_dir_all(local.join("b.txt").join("keep")).await?;
+
+    storify_cmd()
+        .arg("--continue-on-error")
+        .arg("get")
+        .arg("-j")
+        .arg("1")
+        .arg(&remote)
+        .arg(&local)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1 path(s) failed:"))
+        .stderr(predicate::str::contains("b.txt ("));
+
+    assert_eq!(b"a.txt".to_vec(), fs::read(local.join("a.txt")).await?);
+    assert_eq!(b"c.txt".to_vec(), fs::read(local.join("c.txt")).await?);
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_sync_continues_after_failure(client: StorageClient) -> Result<()> {
+    let remote = TEST_FIXTURE.new_dir_path();
+    for name in ["a.txt", "b.txt"] {
+        client
+            .operator()
+            .write(&format!("{remote}{name}"), name.as_bytes().to_vec())
+            .await?;
+    }
+    let local = std::env::temp_dir().join(format!("storify-continue-{}", Uuid::new_v4()));
+    // A directory in the way of a synced file makes that file fail, and its content is
+    // extraneous, so it is still deleted.
+    fs::create_dir_all(local.join("b.txt")).await?;
+    fs::write(local.join("b.txt").join("stale.txt"), b"stale").await?;
+    let failures_file = local.with_extension("json");
+
+    storify_cmd()
+        .arg("--continue-on-error")
+        .arg("--failures-file")
+        .arg(&failures_file)
+        .arg("sync")
+        .arg("--delete")
+        .arg(&remote)
+        .arg(format!("fs://{}/", local.display()))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1 path(s) failed:"))
+        .stderr(predicate::str::contains("b.txt ("));
+
+    assert_eq!(b"a.txt".to_vec(), fs::read(local.join("a.txt")).await?);
+    assert!(!local.join("b.txt").join("stale.txt").exists());
+    let failures = read_failures(&failures_file).await?;
+    assert_eq!(1, failures.len());
+    assert_eq!("sync", failures[0]["action"]);
+
+    let _ = fs::remove_dir_all(&local).await;
+    let _ = fs::remove_file(&failures_file).await;
+    Ok(())
+}
+
+async fn test_delete_failures_file(client: StorageClient) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(client.operator());
+    client.operator().write(&path, content).await?;
+    let missing = format!("{}missing.txt", TEST_FIXTURE.new_dir_path());
+    let failures_file =
+        std::env::temp_dir().join(format!("storify-failures-{}.json", Uuid::new_v4()));
+
+    storify_cmd()
+        .arg("--failures-file")
+        .arg(&failures_file)
+        .arg("rm")
+        .arg("-f")
+        .arg(&missing)
+        .arg(&path)
+        .assert()
+        .failure();
+
+    assert!(!client.operator().exists(&path).await?);
+    let failures = read_failures(&failures_file).await?;
+    assert_eq!(1, failures.len());
+    assert_eq!("delete", failures[0]["action"]);
+    assert_eq!("NotFound", failures[0]["kind"]);
+    assert_eq!(missing.as_str(), failures[0]["path"]);
+
+    let _ = fs::remove_file(&failures_file).await;
+    Ok(())
+}
+
+async fn test_failures_file_empty_on_success(client: StorageClient) -> Result<()> {
+    let src = TEST_FIXTURE.new_dir_path();
+    client
+        .operator()
+        .write(&format!("{src}a.txt"), b"alpha".to_vec())
+        .await?;
+    let dest = TEST_FIXTURE.new_dir_path();
+    let failures_file =
+        std::env::temp_dir().join(format!("storify-failures-{}.json", Uuid::new_v4()));
+
+    storify_cmd()
+        .arg("--continue-on-error")
+        .arg("--failures-file")
+        .arg(&failures_file)
+        .arg("cp")
+        .arg(&src)
+        .arg(&dest)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("failed").not());
+
+    assert!(read_failures(&failures_file).await?.is_empty());
+
+    let _ = fs::remove_file(&failures_file).await;
+    Ok(())
+}
+
+async fn test_pattern_sources_share_one_report(client: StorageClient) -> Result<()> {
+    let remote = TEST_FIXTURE.new_dir_path();
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        client
+            .operator()
+            .write(&format!("{remote}{name}"), name.as_bytes().to_vec())
+            .await?;
+    }
+    let local = std::env::temp_dir().join(format!("storify-continue-{}", Uuid::new_v4()));
+    // Directories in the way make the first and the last matched file fail.
+    fs::create_dir_all(local.join("a.txt").join("keep")).await?;
+    fs::create_dir_all(local.join("c.txt").join("keep")).await?;
+    let failures_file = local.with_extension("json");
+
+    storify_cmd()
+        .arg("--continue-on-error")
+        .arg("--failures-file")
+        .arg(&failures_file)
+        .arg("get")
+        .arg(format!("{remote}*.txt"))
+        .arg(&local)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("2 path(s) failed:"));
+
+    assert_eq!(b"b.txt".to_vec(), fs::read(local.join("b.txt")).await?);
+    let failures = read_failures(&failures_file).await?;
+    let mut paths: Vec<&str> = failures
+        .iter()
+        .map(|f| f["path"].as_str().unwrap())
+        .collect();
+    paths.sort();
+    assert_eq!(
+        vec![format!("{remote}a.txt"), format!("{remote}c.txt")],
+        paths
+    );
+
+    let _ = fs::remove_dir_all(&local).await;
+    let _ = fs::remove_file(&failures_file).await;
+    Ok(())
+}
+
+async fn test_failures_file_cleared_when_command_fails(_client: StorageClient) -> Result<()> {
+    let failures_file =
+        std::env::temp_dir().join(format!("storify-failures-{}.json", Uuid::new_v4()));
+    fs::write(&failures_file, r#"[{"path": "stale"}]"#).await?;
+
+    storify_cmd()
+        .arg("--failures-file")
+        .arg(&failures_file)
+        .arg("--profile")
+        .arg("no-such-profile")
+        .arg("ls")
+        .arg("/")
+        .assert()
+        .failure();
+
+    assert!(read_failures(&failures_file).await?.is_empty());
+
+    let _ = fs::remove_file(&failures_file).await;
+    Ok(())
+}
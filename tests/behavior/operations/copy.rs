@@ -4,6 +4,8 @@ use predicates::prelude::*;
 use std::path::Path;
 use storify::error::Result;
 use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
 
 pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
     tests.extend(async_trials!(
@@ -13,7 +15,13 @@ pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
         test_copy_across_directory,
         test_copy_overwrite_existing_file,
         test_copy_to_nonexistent_directory,
-        test_copy_non_existent_file
+        test_copy_non_existent_file,
+        test_copy_directory_from_local_fs_to_remote,
+        test_copy_file_from_remote_to_local_fs,
+        test_copy_directory_in_parallel,
+        test_copy_within_bucket_is_server_side,
+        test_copy_no_server_side_streams,
+        test_copy_across_endpoints_streams
     ));
 }
 
@@ -169,3 +177,154 @@ async fn test_copy_non_existent_file(client: StorageClient) -> Result<()> {
 
     Ok(())
 }
+
+async fn test_copy_directory_from_local_fs_to_remote(client: StorageClient) -> Result<()> {
+    let local_dir = std::env::temp_dir().join(format!("storify-cp-src-{}", Uuid::new_v4()));
+    fs::create_dir_all(local_dir.join("nested")).await?;
+    fs::write(local_dir.join("a.txt"), b"alpha").await?;
+    fs::write(local_dir.join("nested/b.txt"), b"beta").await?;
+
+    let dest_dir = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("cp")
+        .arg(format!("fs://{}/", local_dir.display()))
+        .arg(&dest_dir)
+        .assert()
+        .success();
+
+    let a = client.operator().read(&format!("{dest_dir}a.txt")).await?;
+    assert_eq!(b"alpha".to_vec(), a.to_vec());
+    let b = client
+        .operator()
+        .read(&format!("{dest_dir}nested/b.txt"))
+        .await?;
+    assert_eq!(b"beta".to_vec(), b.to_vec());
+
+    Ok(())
+}
+
+async fn test_copy_file_from_remote_to_local_fs(client: StorageClient) -> Result<()> {
+    let (src_file, content, _) = TEST_FIXTURE.new_file(client.operator());
+    client.operator().write(&src_file, content.clone()).await?;
+
+    let local_dir = std::env::temp_dir().join(format!("storify-cp-dst-{}", Uuid::new_v4()));
+    let local_file = local_dir.join("copied.bin");
+
+    storify_cmd()
+        .arg("cp")
+        .arg(&src_file)
+        .arg(format!("fs://{}", local_file.display()))
+        .assert()
+        .success();
+
+    assert_eq!(content, fs::read(&local_file).await?);
+    let src_content = client.operator().read(&src_file).await?;
+    assert_eq!(content, src_content.to_vec());
+
+    Ok(())
+}
+
+async fn test_copy_directory_in_parallel(client: StorageClient) -> Result<()> {
+    let src_dir = TEST_FIXTURE.new_dir_path();
+    for i in 0..6 {
+        client
+            .operator()
+            .write(&format!("{src_dir}file_{i}.txt"), format!("content {i}"))
+            .await?;
+    }
+    let dest_dir = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("cp")
+        .arg("--jobs")
+        .arg("4")
+        .arg(&src_dir)
+        .arg(&dest_dir)
+        .assert()
+        .success();
+
+    for i in 0..6 {
+        let content = client
+            .operator()
+            .read(&format!("{dest_dir}file_{i}.txt"))
+            .await?;
+        assert_eq!(format!("content {i}").into_bytes(), content.to_vec());
+    }
+
+    Ok(())
+}
+
+async fn test_copy_within_bucket_is_server_side(client: StorageClient) -> Result<()> {
+    let (src_file, content, _) = TEST_FIXTURE.new_file(client.operator());
+    client.operator().write(&src_file, content.clone()).await?;
+    let dest_file = TEST_FIXTURE.new_file_path();
+
+    storify_cmd()
+        .arg("cp")
+        .arg("--verify")
+        .arg(&src_file)
+        .arg(&dest_file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(server-side)"));
+
+    let dst_content = client.operator().read(&dest_file).await?;
+    assert_eq!(content, dst_content.to_vec());
+    Ok(())
+}
+
+async fn test_copy_no_server_side_streams(client: StorageClient) -> Result<()> {
+    let (src_file, content, _) = TEST_FIXTURE.new_file(client.operator());
+    client.operator().write(&src_file, content.clone()).await?;
+    let dest_file = TEST_FIXTURE.new_file_path();
+
+    storify_cmd()
+        .arg("cp")
+        .arg("--no-server-side")
+        .arg(&src_file)
+        .arg(&dest_file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("server-side").not());
+
+    let dst_content = client.operator().read(&dest_file).await?;
+    assert_eq!(content, dst_content.to_vec());
+    Ok(())
+}
+
+async fn test_copy_across_endpoints_streams(client: StorageClient) -> Result<()> {
+    let (src_file, content, _) = TEST_FIXTURE.new_file(client.operator());
+    client.operator().write(&src_file, content.clone()).await?;
+    let dest_file = TEST_FIXTURE.new_file_path();
+
+    // Same bucket reached through another endpoint: the services may differ, so the
+    // copy cannot be left to the source service.
+    let profile = minio_profile_toml();
+    let other = profile.replace("127.0.0.1", "localhost");
+    assert_ne!(profile, other);
+    let dir = std::env::temp_dir().join(format!("storify-cp-endpoints-{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).await?;
+    let config = dir.join("config.toml");
+    fs::write(
+        &config,
+        format!("[profiles.a]\n{profile}\n[profiles.b]\n{other}"),
+    )
+    .await?;
+
+    bare_storify_cmd()
+        .arg("--config")
+        .arg(&config)
+        .arg("cp")
+        .arg(format!("a:/{src_file}"))
+        .arg(format!("b:/{dest_file}"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("server-side").not());
+
+    let dst_content = client.operator().read(&dest_file).await?;
+    assert_eq!(content, dst_content.to_vec());
+
+    let _ = fs::remove_dir_all(&dir).await;
+    Ok(())
+}
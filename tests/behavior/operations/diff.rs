@@ -0,0 +1,188 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_diff_lists_changes,
+        test_diff_json_and_stat,
+        test_diff_local_against_remote,
+        test_diff_local_trees_by_mtime,
+        test_diff_non_existent_path
+    ));
+}
+
+fn fs_url(path: &Path) -> String {
+    format!("fs://{}/", path.display())
+}
+
+/// Write two remote trees: A has `same`, `grow`, `edit` and `gone`; B has `same`, `grow`
+/// (larger), `edit` (same size, other content) and `new`.
+async fn stage_trees(client: &StorageClient) -> Result<(String, String)> {
+    let a = TEST_FIXTURE.new_dir_path();
+    let b = TEST_FIXTURE.new_dir_path();
+    for (path, content) in [
+        (format!("{a}same.txt"), "same"),
+        (format!("{a}grow.txt"), "small"),
+        (format!("{a}nested/edit.txt"), "abcd"),
+        (format!("{a}gone.txt"), "gone"),
+        (format!("{b}same.txt"), "same"),
+        (format!("{b}grow.txt"), "much larger"),
+        (format!("{b}nested/edit.txt"), "wxyz"),
+        (format!("{b}new.txt"), "new!"),
+    ] {
+        client
+            .operator()
+            .write(&path, content.as_bytes().to_vec())
+            .await?;
+    }
+    Ok((a, b))
+}
+
+async fn test_diff_lists_changes(client: StorageClient) -> Result<()> {
+    let (a, b) = stage_trees(&client).await?;
+
+    storify_cmd()
+        .arg("diff")
+        .arg(&a)
+        .arg(&b)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+ new.txt (4B)"))
+        .stdout(predicate::str::contains("- gone.txt (4B)"))
+        .stdout(predicate::str::contains("M grow.txt (size)"))
+        .stdout(predicate::str::contains("M nested/edit.txt (etag)"))
+        .stdout(predicate::str::contains("same.txt").not())
+        .stdout(predicate::str::contains(
+            "Diff: 1 added (4B), 1 removed (4B), 2 modified, 1 unchanged",
+        ));
+
+    Ok(())
+}
+
+async fn test_diff_json_and_stat(client: StorageClient) -> Result<()> {
+    let (a, b) = stage_trees(&client).await?;
+
+    let output = storify_cmd()
+        .arg("diff")
+        .arg("--json")
+        .arg(&a)
+        .arg(&b)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let diff: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(diff["added"][0]["path"], "new.txt");
+    assert_eq!(diff["removed"][0]["path"], "gone.txt");
+    assert_eq!(diff["modified"][0]["path"], "grow.txt");
+    assert_eq!(diff["modified"][0]["reason"], "size");
+    assert_eq!(diff["modified"][1]["reason"], "etag");
+    assert_eq!(diff["unchanged"], 1);
+
+    storify_cmd()
+        .arg("diff")
+        .arg("--stat")
+        .arg(&a)
+        .arg(&b)
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "Diff: 1 added (4B), 1 removed (4B), 2 modified, 1 unchanged\n",
+        ));
+
+    let output = storify_cmd()
+        .arg("diff")
+        .arg("--stat")
+        .arg("--json")
+        .arg(&a)
+        .arg(&b)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let summary: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(summary["added"], 1);
+    assert_eq!(summary["removed_bytes"], 4);
+    assert_eq!(summary["modified"], 2);
+
+    Ok(())
+}
+
+async fn test_diff_local_against_remote(client: StorageClient) -> Result<()> {
+    let local = std::env::temp_dir().join(format!("storify-diff-{}", Uuid::new_v4()));
+    fs::create_dir_all(&local).await?;
+    fs::write(local.join("a.txt"), b"alpha").await?;
+    fs::write(local.join("b.txt"), b"beta").await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+    client
+        .operator()
+        .write(&format!("{remote}a.txt"), b"alpha".to_vec())
+        .await?;
+
+    storify_cmd()
+        .arg("diff")
+        .arg(fs_url(&local))
+        .arg(&remote)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- b.txt (4B)"))
+        .stdout(predicate::str::contains(
+            "Diff: 0 added (0B), 1 removed (4B), 0 modified, 1 unchanged",
+        ));
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_diff_local_trees_by_mtime(_client: StorageClient) -> Result<()> {
+    let root = std::env::temp_dir().join(format!("storify-diff-{}", Uuid::new_v4()));
+    let (a, b) = (root.join("a"), root.join("b"));
+    for dir in [&a, &b] {
+        fs::create_dir_all(dir).await?;
+        fs::write(dir.join("same.txt"), b"alpha").await?;
+    }
+    // An older copy on the left is as much a difference as a newer one.
+    std::fs::File::options()
+        .write(true)
+        .open(a.join("same.txt"))?
+        .set_modified(SystemTime::now() - Duration::from_secs(3600))?;
+
+    storify_cmd()
+        .arg("diff")
+        .arg(fs_url(&a))
+        .arg(fs_url(&b))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("M same.txt (mtime)"))
+        .stdout(predicate::str::contains(
+            "Diff: 0 added (0B), 0 removed (0B), 1 modified, 0 unchanged",
+        ));
+
+    let _ = fs::remove_dir_all(&root).await;
+    Ok(())
+}
+
+async fn test_diff_non_existent_path(_client: StorageClient) -> Result<()> {
+    let a = TEST_FIXTURE.new_dir_path();
+    let b = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("diff")
+        .arg(&a)
+        .arg(&b)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to diff"));
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use crate::*;
 use assert_cmd::prelude::*;
+use opendal::Operator;
 use predicates::prelude::*;
+use std::path::PathBuf;
 use storify::error::Result;
 use storify::storage::StorageClient;
 use tokio::fs;
@@ -13,7 +15,14 @@ pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
         test_download_directory_recursive,
         test_download_non_existent_file,
         test_download_large_file,
-        test_download_with_special_chars
+        test_download_with_special_chars,
+        test_download_directory_in_parallel,
+        test_download_in_concurrent_ranges,
+        test_download_empty_file,
+        test_download_resume_continues_partial_file,
+        test_download_resume_restarts_when_source_changed,
+        test_download_verify,
+        test_download_verify_detects_corrupt_partial
     ));
 }
 
@@ -187,3 +196,223 @@ async fn test_download_with_special_chars(client: StorageClient) -> Result<()> {
     let _ = fs::remove_dir_all(&local_dir).await;
     Ok(())
 }
+
+async fn test_download_directory_in_parallel(client: StorageClient) -> Result<()> {
+    let remote_dir = TEST_FIXTURE.new_dir_path();
+    for i in 0..6 {
+        client
+            .operator()
+            .write(
+                &format!("{remote_dir}sub/file_{i}.txt"),
+                format!("content {i}"),
+            )
+            .await?;
+    }
+    let local_dest = std::env::temp_dir().join(format!("storify-dl-jobs-{}", Uuid::new_v4()));
+
+    storify_cmd()
+        .arg("get")
+        .arg("-j")
+        .arg("3")
+        .arg(&remote_dir)
+        .arg(&local_dest)
+        .assert()
+        .success();
+
+    for i in 0..6 {
+        let content = fs::read(local_dest.join(format!("sub/file_{i}.txt"))).await?;
+        assert_eq!(format!("content {i}").into_bytes(), content);
+    }
+    // Files are written to temporary siblings and renamed into place
+    let mut entries = fs::read_dir(local_dest.join("sub")).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        assert!(
+            name.starts_with("file_") && name.ends_with(".txt"),
+            "{name}"
+        );
+    }
+
+    let _ = fs::remove_dir_all(&local_dest).await;
+    Ok(())
+}
+
+async fn test_download_in_concurrent_ranges(client: StorageClient) -> Result<()> {
+    let remote_dir = TEST_FIXTURE.new_dir_path();
+    let remote_path = format!("{remote_dir}ranged.bin");
+    let content: Vec<u8> = (0..12 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+    client
+        .operator()
+        .write(&remote_path, content.clone())
+        .await?;
+    let local_dir = std::env::temp_dir().join(format!("storify-dl-ranged-{}", Uuid::new_v4()));
+
+    storify_cmd()
+        .arg("get")
+        .arg("--part-size")
+        .arg("5M")
+        .arg("--concurrency")
+        .arg("3")
+        .arg(&remote_path)
+        .arg(&local_dir)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("100%").not());
+
+    let actual_content = fs::read(local_dir.join("ranged.bin")).await?;
+    assert_eq!(content, actual_content);
+
+    let _ = fs::remove_dir_all(&local_dir).await;
+    Ok(())
+}
+
+async fn test_download_empty_file(client: StorageClient) -> Result<()> {
+    let remote_dir = TEST_FIXTURE.new_dir_path();
+    let remote_path = format!("{remote_dir}empty.txt");
+    client
+        .operator()
+        .write(&remote_path, Vec::<u8>::new())
+        .await?;
+    let local_dir = std::env::temp_dir().join(format!("storify-dl-empty-{}", Uuid::new_v4()));
+
+    storify_cmd()
+        .arg("get")
+        .arg(&remote_path)
+        .arg(&local_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(0 bytes)"));
+
+    let actual_content = fs::read(local_dir.join("empty.txt")).await?;
+    assert!(actual_content.is_empty());
+
+    let _ = fs::remove_dir_all(&local_dir).await;
+    Ok(())
+}
+
+/// Stage a remote file plus a partial download whose first bytes deliberately differ,
+/// so that a resumed download can be told apart from a restarted one.
+///
+/// The object is written through `operator`. Returns the remote path, its content, the
+/// local directory holding the partial copy and the `XDG_STATE_HOME` holding its resume
+/// state.
+async fn stage_partial_download(
+    operator: &Operator,
+    recorded_size: Option<u64>,
+) -> Result<(String, Vec<u8>, PathBuf, PathBuf)> {
+    let remote_dir = TEST_FIXTURE.new_dir_path();
+    let remote_path = format!("{remote_dir}resume.bin");
+    let content: Vec<u8> = (0..64 * 1024).map(|i| (i % 251) as u8).collect();
+    operator.write(&remote_path, content.clone()).await?;
+    let meta = operator.stat(&remote_path).await?;
+
+    let local_dir = std::env::temp_dir().join(format!("storify-dl-resume-{}", Uuid::new_v4()));
+    fs::create_dir_all(&local_dir).await?;
+    fs::write(local_dir.join("resume.bin.storify-part"), vec![0xFF; 1000]).await?;
+    let state = serde_json::json!({
+        "remote_path": remote_path,
+        "size": recorded_size.unwrap_or(meta.content_length()),
+        "etag": meta.etag(),
+        "last_modified": meta.last_modified().map(|t| t.to_rfc3339()),
+    });
+    let state_home = local_dir.with_extension("state");
+    let state_path = resume_state_path(&state_home, &local_dir.join("resume.bin"));
+    fs::create_dir_all(state_path.parent().unwrap()).await?;
+    fs::write(&state_path, serde_json::to_vec(&state)?).await?;
+
+    Ok((remote_path, content, local_dir, state_home))
+}
+
+async fn test_download_resume_continues_partial_file(client: StorageClient) -> Result<()> {
+    let (remote_path, content, local_dir, state_home) =
+        stage_partial_download(client.operator(), None).await?;
+
+    storify_cmd()
+        .env("XDG_STATE_HOME", &state_home)
+        .arg("get")
+        .arg("--resume")
+        .arg(&remote_path)
+        .arg(&local_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("from byte 1000"));
+
+    let actual_content = fs::read(local_dir.join("resume.bin")).await?;
+    assert_eq!(content.len(), actual_content.len());
+    assert_eq!(vec![0xFF; 1000], actual_content[..1000]);
+    assert_eq!(content[1000..], actual_content[1000..]);
+    assert!(!resume_state_path(&state_home, &local_dir.join("resume.bin")).exists());
+    assert!(!local_dir.join("resume.bin.storify-part").exists());
+
+    let _ = fs::remove_dir_all(&local_dir).await;
+    let _ = fs::remove_dir_all(&state_home).await;
+    Ok(())
+}
+
+async fn test_download_resume_restarts_when_source_changed(client: StorageClient) -> Result<()> {
+    let (remote_path, content, local_dir, state_home) =
+        stage_partial_download(client.operator(), Some(1)).await?;
+
+    storify_cmd()
+        .env("XDG_STATE_HOME", &state_home)
+        .arg("get")
+        .arg("--resume")
+        .arg(&remote_path)
+        .arg(&local_dir)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "changed since the interrupted download",
+        ));
+
+    let actual_content = fs::read(local_dir.join("resume.bin")).await?;
+    assert_eq!(content, actual_content);
+
+    let _ = fs::remove_dir_all(&local_dir).await;
+    let _ = fs::remove_dir_all(&state_home).await;
+    Ok(())
+}
+
+async fn test_download_verify(client: StorageClient) -> Result<()> {
+    let staged_file = stage_remote_file(&client).await?;
+    let local_dir = std::env::temp_dir().join(format!("storify-dl-verify-{}", Uuid::new_v4()));
+
+    storify_cmd()
+        .arg("get")
+        .arg("--verify")
+        .arg(&staged_file.remote_path)
+        .arg(&local_dir)
+        .assert()
+        .success();
+
+    let actual_content = fs::read(local_dir.join(&staged_file.file_name)).await?;
+    assert_eq!(staged_file.content, actual_content);
+
+    let _ = fs::remove_dir_all(&local_dir).await;
+    Ok(())
+}
+
+async fn test_download_verify_detects_corrupt_partial(client: StorageClient) -> Result<()> {
+    // The kept partial bytes differ from the remote object, so the checksum cannot match
+    let (remote_path, _, local_dir, state_home) =
+        stage_partial_download(client.operator(), None).await?;
+
+    storify_cmd()
+        .env("XDG_STATE_HOME", &state_home)
+        .arg("get")
+        .arg("--resume")
+        .arg("--verify")
+        .arg(&remote_path)
+        .arg(&local_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Checksum mismatch"))
+        .stderr(predicate::str::contains("expected md5"));
+
+    assert!(!local_dir.join("resume.bin").exists());
+    assert!(!local_dir.join("resume.bin.storify-part").exists());
+
+    let _ = fs::remove_dir_all(&local_dir).await;
+    let _ = fs::remove_dir_all(&state_home).await;
+    Ok(())
+}
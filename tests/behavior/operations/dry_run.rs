@@ -0,0 +1,177 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::PathBuf;
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_dry_run_put_uploads_nothing,
+        test_dry_run_rm_deletes_nothing,
+        test_dry_run_cp_and_mv_leave_trees_unchanged,
+        test_dry_run_sync_with_delete,
+        test_dry_run_get_writes_no_local_files
+    ));
+}
+
+/// Write `a.txt` and `nested/b.txt` under a new remote prefix.
+async fn stage_remote_tree(client: &StorageClient) -> Result<String> {
+    let dir = TEST_FIXTURE.new_dir_path();
+    let op = client.operator();
+    op.write(&format!("{dir}a.txt"), b"alpha".to_vec()).await?;
+    op.write(&format!("{dir}nested/b.txt"), b"bravo!".to_vec())
+        .await?;
+    Ok(dir)
+}
+
+fn temp_path(kind: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("storify-dry-run-{kind}-{}", Uuid::new_v4()))
+}
+
+async fn test_dry_run_put_uploads_nothing(client: StorageClient) -> Result<()> {
+    let local = temp_path("put");
+    fs::create_dir_all(local.join("nested")).await?;
+    fs::write(local.join("a.txt"), b"alpha").await?;
+    fs::write(local.join("nested/b.txt"), b"bravo!").await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("--dry-run")
+        .arg("put")
+        .arg("-R")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Would upload: {} → {remote}a.txt (5 bytes)",
+            local.join("a.txt").display()
+        )))
+        .stdout(predicate::str::contains(format!(
+            "{remote}nested/b.txt (6 bytes)"
+        )))
+        .stdout(predicate::str::contains("Dry run: 2 to upload (11B)"));
+
+    let op = client.operator();
+    assert!(!op.exists(&format!("{remote}a.txt")).await?);
+    assert!(!op.exists(&format!("{remote}nested/b.txt")).await?);
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_dry_run_rm_deletes_nothing(client: StorageClient) -> Result<()> {
+    let remote = stage_remote_tree(&client).await?;
+
+    // No confirmation is asked for, since nothing is deleted.
+    storify_cmd()
+        .arg("rm")
+        .arg("-R")
+        .arg("--dry-run")
+        .arg(&remote)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Would delete: {remote}a.txt"
+        )))
+        .stdout(predicate::str::contains(format!(
+            "Would delete: {remote}nested/b.txt"
+        )))
+        .stdout(predicate::str::contains("Continue?").not());
+
+    let op = client.operator();
+    assert!(op.exists(&format!("{remote}a.txt")).await?);
+    assert!(op.exists(&format!("{remote}nested/b.txt")).await?);
+    Ok(())
+}
+
+async fn test_dry_run_cp_and_mv_leave_trees_unchanged(client: StorageClient) -> Result<()> {
+    let remote = stage_remote_tree(&client).await?;
+    let copy_dest = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("--dry-run")
+        .arg("cp")
+        .arg(&remote)
+        .arg(&copy_dest)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Would copy: {remote}a.txt → {copy_dest}a.txt (5 bytes)"
+        )))
+        .stdout(predicate::str::contains("Dry run: 2 to copy (11B)"));
+
+    let move_dest = format!("{remote}moved.txt");
+    storify_cmd()
+        .arg("--dry-run")
+        .arg("mv")
+        .arg(format!("{remote}a.txt"))
+        .arg(&move_dest)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Would move: {remote}a.txt → {move_dest} (5 bytes)"
+        )))
+        .stdout(predicate::str::contains("Dry run: 1 to move (5B)"));
+
+    let op = client.operator();
+    assert!(!op.exists(&format!("{copy_dest}a.txt")).await?);
+    assert!(op.exists(&format!("{remote}a.txt")).await?);
+    assert!(!op.exists(&move_dest).await?);
+    Ok(())
+}
+
+async fn test_dry_run_sync_with_delete(client: StorageClient) -> Result<()> {
+    let src = stage_remote_tree(&client).await?;
+    let dest = TEST_FIXTURE.new_dir_path();
+    let op = client.operator();
+    op.write(&format!("{dest}stale.txt"), b"stale".to_vec())
+        .await?;
+
+    storify_cmd()
+        .arg("sync")
+        .arg("--delete")
+        .arg("--dry-run")
+        .arg(&src)
+        .arg(&dest)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Would sync: {src}nested/b.txt → {dest}nested/b.txt (6 bytes)"
+        )))
+        .stdout(predicate::str::contains(format!(
+            "Would delete: {dest}stale.txt"
+        )))
+        .stdout(predicate::str::contains(
+            "Dry run: 2 to sync (11B), 1 to delete",
+        ));
+
+    assert!(op.exists(&format!("{dest}stale.txt")).await?);
+    assert!(!op.exists(&format!("{dest}a.txt")).await?);
+    Ok(())
+}
+
+async fn test_dry_run_get_writes_no_local_files(client: StorageClient) -> Result<()> {
+    let remote = stage_remote_tree(&client).await?;
+    let local = temp_path("get");
+
+    storify_cmd()
+        .arg("--dry-run")
+        .arg("get")
+        .arg(&remote)
+        .arg(&local)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Would download: {remote}a.txt → {} (5 bytes)",
+            local.join("a.txt").display()
+        )))
+        .stdout(predicate::str::contains("Dry run: 2 to download (11B)"));
+
+    assert!(!local.exists());
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::PathBuf;
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_put_recursive_with_include_and_exclude,
+        test_get_with_exclude_from_file,
+        test_copy_with_path_pattern,
+        test_rm_recursive_deletes_only_selected_files,
+        test_ls_and_du_with_filters,
+        test_invalid_pattern_is_rejected
+    ));
+}
+
+/// Write `keep.txt`, `skip.log` and `nested/deep.txt` under a new remote prefix.
+async fn stage_remote_tree(client: &StorageClient) -> Result<String> {
+    let dir = TEST_FIXTURE.new_dir_path();
+    for (name, content) in [
+        ("keep.txt", "keep"),
+        ("skip.log", "skip"),
+        ("nested/deep.txt", "deep"),
+    ] {
+        client
+            .operator()
+            .write(&format!("{dir}{name}"), content.as_bytes().to_vec())
+            .await?;
+    }
+    Ok(dir)
+}
+
+fn temp_path(kind: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("storify-filter-{kind}-{}", Uuid::new_v4()))
+}
+
+async fn test_put_recursive_with_include_and_exclude(client: StorageClient) -> Result<()> {
+    let local = temp_path("put");
+    fs::create_dir_all(local.join("nested")).await?;
+    fs::write(local.join("a.txt"), b"a").await?;
+    fs::write(local.join("b.log"), b"b").await?;
+    fs::write(local.join("nested/c.txt"), b"c").await?;
+    fs::write(local.join("nested/secret.txt"), b"s").await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("put")
+        .arg("-R")
+        .arg("--include")
+        .arg("*.txt")
+        .arg("--exclude")
+        .arg("secret*")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .success();
+
+    let op = client.operator();
+    assert!(op.exists(&format!("{remote}a.txt")).await?);
+    assert!(op.exists(&format!("{remote}nested/c.txt")).await?);
+    assert!(!op.exists(&format!("{remote}b.log")).await?);
+    assert!(!op.exists(&format!("{remote}nested/secret.txt")).await?);
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_get_with_exclude_from_file(client: StorageClient) -> Result<()> {
+    let remote = stage_remote_tree(&client).await?;
+    let patterns = temp_path("patterns");
+    fs::write(&patterns, "# logs are noise\n*.log\n\nnested/**\n").await?;
+    let local = temp_path("get");
+
+    storify_cmd()
+        .arg("get")
+        .arg("--exclude-from")
+        .arg(&patterns)
+        .arg(&remote)
+        .arg(&local)
+        .assert()
+        .success();
+
+    assert_eq!(b"keep".to_vec(), fs::read(local.join("keep.txt")).await?);
+    assert!(!local.join("skip.log").exists());
+    assert!(!local.join("nested/deep.txt").exists());
+
+    let _ = fs::remove_dir_all(&local).await;
+    let _ = fs::remove_file(&patterns).await;
+    Ok(())
+}
+
+async fn test_copy_with_path_pattern(client: StorageClient) -> Result<()> {
+    let src = stage_remote_tree(&client).await?;
+    let dest = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("cp")
+        .arg("--include")
+        .arg("nested/*.txt")
+        .arg(&src)
+        .arg(&dest)
+        .assert()
+        .success();
+
+    let op = client.operator();
+    assert!(op.exists(&format!("{dest}nested/deep.txt")).await?);
+    assert!(!op.exists(&format!("{dest}keep.txt")).await?);
+    assert!(!op.exists(&format!("{dest}skip.log")).await?);
+
+    Ok(())
+}
+
+async fn test_rm_recursive_deletes_only_selected_files(client: StorageClient) -> Result<()> {
+    let dir = stage_remote_tree(&client).await?;
+
+    storify_cmd()
+        .arg("rm")
+        .arg("-R")
+        .arg("-f")
+        .arg("--exclude")
+        .arg("*.txt")
+        .arg(&dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("skip.log"))
+        .stdout(predicate::str::contains("keep.txt").not());
+
+    let op = client.operator();
+    assert!(!op.exists(&format!("{dir}skip.log")).await?);
+    assert!(op.exists(&format!("{dir}keep.txt")).await?);
+    assert!(op.exists(&format!("{dir}nested/deep.txt")).await?);
+
+    Ok(())
+}
+
+async fn test_ls_and_du_with_filters(client: StorageClient) -> Result<()> {
+    let dir = stage_remote_tree(&client).await?;
+
+    storify_cmd()
+        .arg("ls")
+        .arg("-R")
+        .arg("--include")
+        .arg("*.txt")
+        .arg(&dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("keep.txt"))
+        .stdout(predicate::str::contains("nested/deep.txt"))
+        .stdout(predicate::str::contains("skip.log").not());
+
+    storify_cmd()
+        .arg("du")
+        .arg("-s")
+        .arg("--exclude")
+        .arg("*.txt")
+        .arg(&dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("4B"));
+
+    Ok(())
+}
+
+async fn test_invalid_pattern_is_rejected(_client: StorageClient) -> Result<()> {
+    let dir = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("ls")
+        .arg("--include")
+        .arg("[unclosed")
+        .arg(&dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid value '[unclosed' for --include",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use storify::error::Result;
+use storify::storage::StorageClient;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_find_by_name_and_type,
+        test_find_by_size_and_mtime,
+        test_find_print0,
+        test_find_delete,
+        test_find_rejects_invalid_predicate,
+        test_find_after_global_options_with_values
+    ));
+}
+
+/// Write `app.log` (10 bytes), `App.LOG` (2000 bytes) and `logs/db.log` (100 bytes).
+async fn stage_tree(client: &StorageClient) -> Result<String> {
+    let dir = TEST_FIXTURE.new_dir_path();
+    for (name, size) in [("app.log", 10), ("App.LOG", 2000), ("logs/db.log", 100)] {
+        client
+            .operator()
+            .write(&format!("{dir}{name}"), vec![b'x'; size])
+            .await?;
+    }
+    Ok(dir)
+}
+
+async fn test_find_by_name_and_type(client: StorageClient) -> Result<()> {
+    let dir = stage_tree(&client).await?;
+
+    storify_cmd()
+        .arg("find")
+        .arg(&dir)
+        .arg("-name")
+        .arg("*.log")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("{dir}app.log\n")))
+        .stdout(predicate::str::contains(format!("{dir}logs/db.log\n")))
+        .stdout(predicate::str::contains("App.LOG").not());
+
+    storify_cmd()
+        .arg("find")
+        .arg(&dir)
+        .arg("-iname")
+        .arg("app.log")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("{dir}app.log\n")))
+        .stdout(predicate::str::contains(format!("{dir}App.LOG\n")))
+        .stdout(predicate::str::contains("db.log").not());
+
+    storify_cmd()
+        .arg("find")
+        .arg(&dir)
+        .arg("-type")
+        .arg("d")
+        .assert()
+        .success()
+        .stdout(predicate::eq(format!("{dir}logs/\n")));
+
+    storify_cmd()
+        .arg("find")
+        .arg(&dir)
+        .arg("-type")
+        .arg("f")
+        .arg("-name")
+        .arg("logs")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    Ok(())
+}
+
+async fn test_find_by_size_and_mtime(client: StorageClient) -> Result<()> {
+    let dir = stage_tree(&client).await?;
+
+    storify_cmd()
+        .arg("find")
+        .arg(&dir)
+        .arg("-size")
+        .arg("+1K")
+        .assert()
+        .success()
+        .stdout(predicate::eq(format!("{dir}App.LOG\n")));
+
+    storify_cmd()
+        .arg("find")
+        .arg(&dir)
+        .arg("-type")
+        .arg("f")
+        .arg("-size")
+        .arg("-50")
+        .assert()
+        .success()
+        .stdout(predicate::eq(format!("{dir}app.log\n")));
+
+    storify_cmd()
+        .arg("find")
+        .arg(&dir)
+        .arg("-type")
+        .arg("f")
+        .arg("-mtime")
+        .arg("-1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("app.log"))
+        .stdout(predicate::str::contains("App.LOG"))
+        .stdout(predicate::str::contains("db.log"));
+
+    storify_cmd()
+        .arg("find")
+        .arg(&dir)
+        .arg("-mtime")
+        .arg("+7")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    Ok(())
+}
+
+async fn test_find_print0(client: StorageClient) -> Result<()> {
+    let dir = stage_tree(&client).await?;
+
+    storify_cmd()
+        .arg("find")
+        .arg(&dir)
+        .arg("-name")
+        .arg("*.log")
+        .arg("-print0")
+        .assert()
+        .success()
+        .stdout(predicate::eq(format!("{dir}app.log\0{dir}logs/db.log\0")));
+
+    Ok(())
+}
+
+async fn test_find_delete(client: StorageClient) -> Result<()> {
+    let dir = stage_tree(&client).await?;
+
+    assert_cmd::Command::from_std(storify_cmd())
+        .arg("find")
+        .arg(&dir)
+        .arg("-name")
+        .arg("*.log")
+        .arg("-delete")
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Operation cancelled."));
+    assert!(client.operator().exists(&format!("{dir}app.log")).await?);
+
+    storify_cmd()
+        .arg("find")
+        .arg(&dir)
+        .arg("-name")
+        .arg("*.log")
+        .arg("-delete")
+        .arg("-f")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("Deleted: {dir}app.log")));
+
+    let op = client.operator();
+    assert!(!op.exists(&format!("{dir}app.log")).await?);
+    assert!(!op.exists(&format!("{dir}logs/db.log")).await?);
+    assert!(op.exists(&format!("{dir}App.LOG")).await?);
+
+    Ok(())
+}
+
+async fn test_find_rejects_invalid_predicate(_client: StorageClient) -> Result<()> {
+    let dir = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("find")
+        .arg(&dir)
+        .arg("-type")
+        .arg("x")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid value 'x' for -type"));
+
+    Ok(())
+}
+
+async fn test_find_after_global_options_with_values(client: StorageClient) -> Result<()> {
+    let dir = stage_tree(&client).await?;
+
+    storify_cmd()
+        .args(["--output", "json", "--retries", "3", "--timeout", "30"])
+        .args(["--io-timeout", "30", "--limit-rate", "10M"])
+        .arg("find")
+        .arg(&dir)
+        .arg("-name")
+        .arg("*.log")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("{dir}app.log\n")))
+        .stdout(predicate::str::contains("App.LOG").not());
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_ls_expands_nested_pattern,
+        test_rm_expands_pattern,
+        test_get_expands_pattern,
+        test_cp_mv_pattern_needs_directory_target,
+        test_glob_without_match_fails,
+        test_no_glob_keeps_metacharacters_literal
+    ));
+}
+
+/// Write a small partitioned tree under a new remote prefix.
+async fn stage_partitions(client: &StorageClient) -> Result<String> {
+    let dir = TEST_FIXTURE.new_dir_path();
+    for name in [
+        "2025-01/part-0.parquet",
+        "2025-01/part-1.parquet",
+        "2025-01/_SUCCESS",
+        "2025-02/part-0.parquet",
+        "2024-12/part-0.parquet",
+        "app.log",
+        "db.log",
+        "notes.txt",
+    ] {
+        client
+            .operator()
+            .write(&format!("{dir}{name}"), name.as_bytes().to_vec())
+            .await?;
+    }
+    Ok(dir)
+}
+
+async fn test_ls_expands_nested_pattern(client: StorageClient) -> Result<()> {
+    let dir = stage_partitions(&client).await?;
+
+    storify_cmd()
+        .arg("ls")
+        .arg(format!("{dir}2025-*/part-*.parquet"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2025-01/part-0.parquet"))
+        .stdout(predicate::str::contains("2025-01/part-1.parquet"))
+        .stdout(predicate::str::contains("2025-02/part-0.parquet"))
+        .stdout(predicate::str::contains("2024-12").not())
+        .stdout(predicate::str::contains("_SUCCESS").not());
+
+    storify_cmd()
+        .arg("du")
+        .arg("-s")
+        .arg(format!("{dir}**/*.parquet"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2024-12/part-0.parquet"));
+
+    Ok(())
+}
+
+async fn test_rm_expands_pattern(client: StorageClient) -> Result<()> {
+    let dir = stage_partitions(&client).await?;
+
+    storify_cmd()
+        .arg("rm")
+        .arg("-f")
+        .arg(format!("{dir}*.log"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("app.log"))
+        .stdout(predicate::str::contains("db.log"));
+
+    let op = client.operator();
+    assert!(!op.exists(&format!("{dir}app.log")).await?);
+    assert!(!op.exists(&format!("{dir}db.log")).await?);
+    assert!(op.exists(&format!("{dir}notes.txt")).await?);
+
+    Ok(())
+}
+
+async fn test_get_expands_pattern(client: StorageClient) -> Result<()> {
+    let dir = stage_partitions(&client).await?;
+    let local = std::env::temp_dir().join(format!("storify-glob-{}", Uuid::new_v4()));
+
+    storify_cmd()
+        .arg("get")
+        .arg(format!("{dir}2025-01/part-?.parquet"))
+        .arg(&local)
+        .assert()
+        .success();
+
+    assert!(local.join("part-0.parquet").exists());
+    assert!(local.join("part-1.parquet").exists());
+    assert!(!local.join("_SUCCESS").exists());
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_cp_mv_pattern_needs_directory_target(client: StorageClient) -> Result<()> {
+    let dir = stage_partitions(&client).await?;
+    let target = format!("{}target", TEST_FIXTURE.new_dir_path());
+
+    for command in ["cp", "mv"] {
+        storify_cmd()
+            .arg(command)
+            .arg(format!("{dir}*.log"))
+            .arg(&target)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("is not a directory"));
+    }
+    let op = client.operator();
+    assert!(!op.exists(&target).await?);
+    assert!(op.exists(&format!("{dir}app.log")).await?);
+
+    let target_dir = TEST_FIXTURE.new_dir_path();
+    op.write(&format!("{target_dir}keep.txt"), b"keep".to_vec())
+        .await?;
+    storify_cmd()
+        .arg("cp")
+        .arg(format!("{dir}*.log"))
+        .arg(target_dir.trim_end_matches('/'))
+        .assert()
+        .success();
+    assert!(op.exists(&format!("{target_dir}app.log")).await?);
+    assert!(op.exists(&format!("{target_dir}db.log")).await?);
+
+    Ok(())
+}
+
+async fn test_glob_without_match_fails(client: StorageClient) -> Result<()> {
+    let dir = stage_partitions(&client).await?;
+
+    storify_cmd()
+        .arg("stat")
+        .arg(format!("{dir}*.csv"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No paths match"));
+
+    storify_cmd()
+        .arg("diff")
+        .arg(format!("{dir}2025-*"))
+        .arg(&dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("matches 2 paths"));
+
+    Ok(())
+}
+
+async fn test_no_glob_keeps_metacharacters_literal(client: StorageClient) -> Result<()> {
+    let dir = TEST_FIXTURE.new_dir_path();
+    let literal = format!("{dir}report[1].txt");
+    client
+        .operator()
+        .write(&literal, b"literal".to_vec())
+        .await?;
+
+    storify_cmd()
+        .arg("cat")
+        .arg(&literal)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No paths match"));
+
+    storify_cmd()
+        .arg("cat")
+        .arg("--no-glob")
+        .arg(&literal)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("literal"));
+
+    Ok(())
+}
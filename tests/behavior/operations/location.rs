@@ -0,0 +1,81 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_bucket_url_path,
+        test_profile_prefixed_path,
+        test_fs_url_path,
+        test_unsupported_scheme_fails
+    ));
+}
+
+async fn test_bucket_url_path(client: StorageClient) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(client.operator());
+    client.operator().write(&path, content.clone()).await?;
+    let env = E2eTestEnv::new().await;
+
+    storify_cmd()
+        .arg("stat")
+        .arg(format!("s3://{}/{}", env.config.bucket, path))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("type=file"))
+        .stdout(predicate::str::contains(format!("size={}", content.len())));
+
+    Ok(())
+}
+
+async fn test_profile_prefixed_path(client: StorageClient) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(client.operator());
+    client.operator().write(&path, content).await?;
+
+    let dir = std::env::temp_dir().join(format!("storify-loc-{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).await?;
+    let config = dir.join("config.toml");
+    fs::write(&config, format!("[profiles.dev]\n{}", minio_profile_toml())).await?;
+
+    bare_storify_cmd()
+        .arg("--config")
+        .arg(&config)
+        .arg("stat")
+        .arg(format!("dev:/{path}"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("type=file"));
+
+    Ok(())
+}
+
+async fn test_fs_url_path(_client: StorageClient) -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("storify-loc-fs-{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).await?;
+    let file = dir.join("hello.txt");
+    fs::write(&file, b"hello from fs").await?;
+
+    bare_storify_cmd()
+        .arg("cat")
+        .arg(format!("fs://{}", file.display()))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hello from fs"));
+
+    Ok(())
+}
+
+async fn test_unsupported_scheme_fails(_client: StorageClient) -> Result<()> {
+    storify_cmd()
+        .arg("ls")
+        .arg("ftp://bucket/key")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsupported storage provider"));
+
+    Ok(())
+}
@@ -1,10 +1,25 @@
 pub mod cat;
+pub mod checksum;
+pub mod config;
+pub mod continue_on_error;
 pub mod copy;
 pub mod delete;
+pub mod diff;
 pub mod download;
+pub mod dry_run;
+pub mod filter;
+pub mod find;
+pub mod glob;
 pub mod list;
+pub mod location;
 pub mod mkdir;
 pub mod mv;
+pub mod output;
+pub mod progress;
+pub mod retry;
 pub mod stat;
+pub mod sync;
+pub mod throttle;
 pub mod upload;
 pub mod usage;
+pub mod verify;
@@ -4,6 +4,8 @@ use predicates::prelude::*;
 use std::path::Path;
 use storify::error::Result;
 use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
 
 pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
     tests.extend(async_trials!(
@@ -12,7 +14,10 @@ pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
         test_move_across_directory,
         test_move_rename_existing_file,
         test_move_to_nonexistent_directory,
-        test_move_non_existent_file
+        test_move_non_existent_file,
+        test_move_file_from_local_fs_to_remote,
+        test_move_with_verify_to_local_fs,
+        test_move_within_local_fs_renames
     ));
 }
 
@@ -166,3 +171,71 @@ async fn test_move_non_existent_file(client: StorageClient) -> Result<()> {
 
     Ok(())
 }
+
+async fn test_move_file_from_local_fs_to_remote(client: StorageClient) -> Result<()> {
+    let local_dir = std::env::temp_dir().join(format!("storify-mv-src-{}", Uuid::new_v4()));
+    fs::create_dir_all(&local_dir).await?;
+    let local_file = local_dir.join("moved.txt");
+    fs::write(&local_file, b"moving across storages").await?;
+
+    let dest_path = TEST_FIXTURE.new_file_path();
+
+    storify_cmd()
+        .arg("mv")
+        .arg(format!("fs://{}", local_file.display()))
+        .arg(&dest_path)
+        .assert()
+        .success();
+
+    let dest_content = client.operator().read(&dest_path).await?;
+    assert_eq!(b"moving across storages".to_vec(), dest_content.to_vec());
+    assert!(!local_file.exists());
+
+    Ok(())
+}
+
+async fn test_move_with_verify_to_local_fs(client: StorageClient) -> Result<()> {
+    let (src_path, content, _) = TEST_FIXTURE.new_file(client.operator());
+    client.operator().write(&src_path, content.clone()).await?;
+    let local_dir = std::env::temp_dir().join(format!("storify-mv-verify-{}", Uuid::new_v4()));
+    fs::create_dir_all(&local_dir).await?;
+
+    // The local filesystem has no ETags, so the destination hash is recomputed
+    storify_cmd()
+        .arg("mv")
+        .arg("--verify")
+        .arg(&src_path)
+        .arg(format!("fs://{}/", local_dir.display()))
+        .assert()
+        .success();
+
+    let file_name = Path::new(&src_path).file_name().unwrap();
+    let dest_content = fs::read(local_dir.join(file_name)).await?;
+    assert_eq!(content, dest_content);
+    assert!(!client.operator().exists(&src_path).await?);
+
+    let _ = fs::remove_dir_all(&local_dir).await;
+    Ok(())
+}
+
+async fn test_move_within_local_fs_renames(_client: StorageClient) -> Result<()> {
+    let local_dir = std::env::temp_dir().join(format!("storify-mv-rename-{}", Uuid::new_v4()));
+    fs::create_dir_all(&local_dir).await?;
+    let src = local_dir.join("before.txt");
+    let dest = local_dir.join("after.txt");
+    fs::write(&src, b"renamed in place").await?;
+
+    storify_cmd()
+        .arg("mv")
+        .arg(format!("fs://{}", src.display()))
+        .arg(format!("fs://{}", dest.display()))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(server-side rename)"));
+
+    assert_eq!(b"renamed in place".to_vec(), fs::read(&dest).await?);
+    assert!(!src.exists());
+
+    let _ = fs::remove_dir_all(&local_dir).await;
+    Ok(())
+}
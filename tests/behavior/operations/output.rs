@@ -0,0 +1,216 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::path::PathBuf;
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_ls_json_output,
+        test_du_summary_jsonl_output,
+        test_put_jsonl_output,
+        test_rm_json_output_reports_failures,
+        test_single_item_failures_are_records,
+        test_mkdir_raw_output,
+        test_invalid_output_format
+    ));
+}
+
+/// Write `a.txt` and `nested/b.txt` under a new remote prefix.
+async fn stage_remote_tree(client: &StorageClient) -> Result<String> {
+    let dir = TEST_FIXTURE.new_dir_path();
+    let op = client.operator();
+    op.write(&format!("{dir}a.txt"), b"alpha".to_vec()).await?;
+    op.write(&format!("{dir}nested/b.txt"), b"bravo!".to_vec())
+        .await?;
+    Ok(dir)
+}
+
+fn temp_path(kind: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("storify-output-{kind}-{}", Uuid::new_v4()))
+}
+
+fn json_lines(stdout: &[u8]) -> Vec<Value> {
+    String::from_utf8_lossy(stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("every line is a JSON object"))
+        .collect()
+}
+
+async fn test_ls_json_output(client: StorageClient) -> Result<()> {
+    let remote = stage_remote_tree(&client).await?;
+
+    let out = storify_cmd()
+        .arg("ls")
+        .arg("-R")
+        .arg("--output")
+        .arg("json")
+        .arg(&remote)
+        .output()?;
+    assert!(out.status.success());
+
+    let items: Value = serde_json::from_slice(&out.stdout)?;
+    let items = items.as_array().expect("ls --output json prints an array");
+    let file = items
+        .iter()
+        .find(|item| item["path"] == format!("{remote}a.txt"))
+        .expect("a.txt is listed");
+    assert_eq!(file["type"], "file");
+    assert_eq!(file["size"], 5);
+    assert!(file.get("last_modified").is_some());
+    Ok(())
+}
+
+async fn test_du_summary_jsonl_output(client: StorageClient) -> Result<()> {
+    let remote = stage_remote_tree(&client).await?;
+
+    let out = storify_cmd()
+        .arg("--output")
+        .arg("jsonl")
+        .arg("du")
+        .arg("-s")
+        .arg(&remote)
+        .output()?;
+    assert!(out.status.success());
+
+    let lines = json_lines(&out.stdout);
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["path"], remote);
+    assert_eq!(lines[0]["size"], 11);
+    assert_eq!(lines[0]["files"], 2);
+    Ok(())
+}
+
+async fn test_put_jsonl_output(client: StorageClient) -> Result<()> {
+    let local = temp_path("put");
+    fs::create_dir_all(local.join("nested")).await?;
+    fs::write(local.join("a.txt"), b"alpha").await?;
+    fs::write(local.join("nested/b.txt"), b"bravo!").await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+
+    let out = storify_cmd()
+        .arg("--output")
+        .arg("jsonl")
+        .arg("put")
+        .arg("-R")
+        .arg(&local)
+        .arg(&remote)
+        .output()?;
+    assert!(out.status.success());
+
+    let mut lines = json_lines(&out.stdout);
+    lines.sort_by_key(|line| line["dest"].as_str().unwrap_or_default().to_string());
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["action"], "upload");
+    assert_eq!(lines[0]["status"], "ok");
+    assert_eq!(lines[0]["dest"], format!("{remote}a.txt"));
+    assert_eq!(lines[0]["bytes"], 5);
+    assert_eq!(lines[1]["dest"], format!("{remote}nested/b.txt"));
+    assert!(client.operator().exists(&format!("{remote}a.txt")).await?);
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_rm_json_output_reports_failures(client: StorageClient) -> Result<()> {
+    let remote = stage_remote_tree(&client).await?;
+    let missing = format!("{remote}missing.txt");
+    let existing = format!("{remote}a.txt");
+
+    let out = storify_cmd()
+        .arg("rm")
+        .arg("-f")
+        .arg("--output")
+        .arg("json")
+        .arg(&missing)
+        .arg(&existing)
+        .output()?;
+    assert!(!out.status.success());
+
+    let items: Value = serde_json::from_slice(&out.stdout)?;
+    let items = items.as_array().expect("rm --output json prints an array");
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["path"], missing);
+    assert_eq!(items[0]["status"], "missing");
+    assert_eq!(items[1]["path"], existing);
+    assert_eq!(items[1]["status"], "ok");
+    assert!(!client.operator().exists(&existing).await?);
+    Ok(())
+}
+
+async fn test_single_item_failures_are_records(client: StorageClient) -> Result<()> {
+    let remote = stage_remote_tree(&client).await?;
+    let missing = format!("{remote}missing.txt");
+    let local = temp_path("get");
+
+    let out = storify_cmd()
+        .args(["--output", "jsonl", "get"])
+        .arg(&missing)
+        .arg(&local)
+        .output()?;
+    assert!(!out.status.success());
+    let items = json_lines(&out.stdout);
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["action"], "download");
+    assert_eq!(items[0]["status"], "failed");
+    assert_eq!(items[0]["src"], missing);
+    assert!(items[0]["error"].as_str().is_some());
+
+    let dest = format!("{remote}copy.txt");
+    let out = storify_cmd()
+        .args(["--output", "json", "cp"])
+        .arg(&missing)
+        .arg(&dest)
+        .output()?;
+    assert!(!out.status.success());
+    let items: Value = serde_json::from_slice(&out.stdout)?;
+    let items = items.as_array().expect("cp --output json prints an array");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["action"], "copy");
+    assert_eq!(items[0]["status"], "failed");
+    assert_eq!(items[0]["dest"], dest);
+
+    let out = storify_cmd()
+        .args(["--output", "jsonl", "rm", "-f"])
+        .arg(format!("{remote}nested/"))
+        .output()?;
+    assert!(!out.status.success());
+    let items = json_lines(&out.stdout);
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["action"], "delete");
+    assert_eq!(items[0]["status"], "failed");
+    Ok(())
+}
+
+async fn test_mkdir_raw_output(_client: StorageClient) -> Result<()> {
+    let dir = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("mkdir")
+        .arg("--output")
+        .arg("raw")
+        .arg(&dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "action=mkdir status=ok path={dir}"
+        )));
+    Ok(())
+}
+
+async fn test_invalid_output_format(_client: StorageClient) -> Result<()> {
+    storify_cmd()
+        .arg("--output")
+        .arg("yaml")
+        .arg("ls")
+        .arg("/")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--output"));
+    Ok(())
+}
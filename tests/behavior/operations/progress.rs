@@ -0,0 +1,196 @@
+use crate::*;
+use std::sync::{Arc, Mutex};
+use storify::error::Result;
+use storify::storage::{
+    Action, Progress, ProgressEvent, ProgressObserver, StorageClient, TransferOptions,
+};
+use tokio::fs;
+use uuid::Uuid;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_progress_events_of_recursive_upload,
+        test_progress_events_of_single_download,
+        test_progress_events_carry_resume_warnings,
+        test_progress_observer_of_client
+    ));
+}
+
+/// Records every event as a short line, to compare against the expected sequence.
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<String>>,
+}
+
+impl ProgressObserver for Recorder {
+    fn on_event(&self, event: ProgressEvent<'_>) {
+        let line = match event {
+            ProgressEvent::JobStarted { files, bytes } => format!("job {files} {bytes}"),
+            ProgressEvent::FileStarted { action, path, size } => {
+                let action = match action {
+                    Action::Upload => "upload",
+                    Action::Download => "download",
+                    _ => "other",
+                };
+                format!("start {action} {} {size}", file_name(path))
+            }
+            // Intermediate updates depend on chunking; only their bounds are checked.
+            ProgressEvent::BytesTransferred { bytes, .. } => {
+                assert!(bytes <= 5);
+                return;
+            }
+            ProgressEvent::FileFinished { path, bytes } => {
+                format!("finish {} {bytes}", file_name(path))
+            }
+            ProgressEvent::FileFailed { path, .. } => format!("failed {path}"),
+            ProgressEvent::FileResumed { path, offset } => {
+                format!("resumed {} {offset}", file_name(path))
+            }
+            ProgressEvent::Warning { message } => format!("warning {message}"),
+            ProgressEvent::JobFinished => "done".to_string(),
+        };
+        self.events.lock().unwrap().push(line);
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+async fn test_progress_events_of_recursive_upload(client: StorageClient) -> Result<()> {
+    let local = std::env::temp_dir().join(format!("storify-progress-{}", Uuid::new_v4()));
+    fs::create_dir_all(&local).await?;
+    fs::write(local.join("a.txt"), b"alpha").await?;
+    fs::write(local.join("b.txt"), b"beta").await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+
+    let recorder = Arc::new(Recorder::default());
+    let options = TransferOptions {
+        jobs: 1,
+        progress: Progress::new(recorder.clone()),
+        ..TransferOptions::default()
+    };
+    client
+        .upload_files(&local.to_string_lossy(), &remote, true, &options)
+        .await?;
+
+    let mut events = recorder.events.lock().unwrap().clone();
+    assert_eq!("job 2 9", events.remove(0));
+    assert_eq!(Some("done".to_string()), events.pop());
+    events.sort();
+    assert_eq!(
+        vec![
+            "finish a.txt 5",
+            "finish b.txt 4",
+            "start upload a.txt 5",
+            "start upload b.txt 4"
+        ],
+        events
+    );
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_progress_events_of_single_download(client: StorageClient) -> Result<()> {
+    let remote = format!("{}hello.txt", TEST_FIXTURE.new_dir_path());
+    client.operator().write(&remote, b"hello".to_vec()).await?;
+    let local = std::env::temp_dir().join(format!("storify-progress-{}", Uuid::new_v4()));
+    fs::create_dir_all(&local).await?;
+
+    let recorder = Arc::new(Recorder::default());
+    let options = TransferOptions {
+        progress: Progress::new(recorder.clone()),
+        ..TransferOptions::default()
+    };
+    client
+        .download_files(&remote, &local.to_string_lossy(), &options)
+        .await?;
+
+    assert_eq!(
+        vec![
+            "job 1 5",
+            "start download hello.txt 5",
+            "finish hello.txt 5",
+            "done"
+        ],
+        *recorder.events.lock().unwrap()
+    );
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_progress_events_carry_resume_warnings(client: StorageClient) -> Result<()> {
+    let remote = format!("{}hello.txt", TEST_FIXTURE.new_dir_path());
+    client.operator().write(&remote, b"hello".to_vec()).await?;
+    let local = std::env::temp_dir().join(format!("storify-progress-{}", Uuid::new_v4()));
+    fs::create_dir_all(&local).await?;
+    // A partial download nobody recorded the source of cannot be trusted.
+    fs::write(local.join("hello.txt.storify-part"), b"he").await?;
+
+    let recorder = Arc::new(Recorder::default());
+    let options = TransferOptions {
+        resume: true,
+        progress: Progress::new(recorder.clone()),
+        ..TransferOptions::default()
+    };
+    client
+        .download_files(&remote, &local.to_string_lossy(), &options)
+        .await?;
+
+    let events = recorder.events.lock().unwrap().clone();
+    assert!(
+        events
+            .iter()
+            .any(|e| e.starts_with("warning no resume state for ") && e.ends_with("restarting")),
+        "{events:?}"
+    );
+    assert!(!events.iter().any(|e| e.starts_with("resumed")));
+    assert_eq!(b"hello".to_vec(), fs::read(local.join("hello.txt")).await?);
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_progress_observer_of_client(client: StorageClient) -> Result<()> {
+    let remote = format!("{}hello.txt", TEST_FIXTURE.new_dir_path());
+    client.operator().write(&remote, b"hello".to_vec()).await?;
+    let local = std::env::temp_dir().join(format!("storify-progress-{}", Uuid::new_v4()));
+    fs::create_dir_all(&local).await?;
+
+    let recorder = Arc::new(Recorder::default());
+    let observed = client.clone().with_progress_observer(recorder.clone());
+    observed
+        .download_files(
+            &remote,
+            &local.to_string_lossy(),
+            &TransferOptions::default(),
+        )
+        .await?;
+    assert_eq!(
+        vec![
+            "job 1 5",
+            "start download hello.txt 5",
+            "finish hello.txt 5",
+            "done"
+        ],
+        *recorder.events.lock().unwrap()
+    );
+
+    // Options bringing their own observer take precedence over the client's.
+    let own = Arc::new(Recorder::default());
+    let options = TransferOptions {
+        progress: Progress::new(own.clone()),
+        ..TransferOptions::default()
+    };
+    observed
+        .download_files(&remote, &local.to_string_lossy(), &options)
+        .await?;
+    assert_eq!(4, recorder.events.lock().unwrap().len());
+    assert_eq!(4, own.events.lock().unwrap().len());
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::time::{Duration, Instant};
+use storify::config::{ConfigLoader, RequestOverrides};
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+/// An endpoint nothing listens on, so every request fails with a temporary error.
+const UNREACHABLE_ENDPOINT: &str = "http://127.0.0.1:1";
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_retries_unreachable_endpoint,
+        test_no_retries_fails_at_once,
+        test_missing_path_is_not_retried,
+        test_profile_request_settings,
+        test_rejects_zero_timeout
+    ));
+}
+
+async fn test_retries_unreachable_endpoint(_client: StorageClient) -> Result<()> {
+    let started = Instant::now();
+    storify_cmd()
+        .env("STORAGE_ENDPOINT", UNREACHABLE_ENDPOINT)
+        .arg("--retries")
+        .arg("1")
+        .arg("ls")
+        .arg("/")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("persistent"));
+
+    // The retry backs off for at least a second before trying again.
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    Ok(())
+}
+
+async fn test_no_retries_fails_at_once(_client: StorageClient) -> Result<()> {
+    storify_cmd()
+        .env("STORAGE_ENDPOINT", UNREACHABLE_ENDPOINT)
+        .arg("--retries")
+        .arg("0")
+        .arg("ls")
+        .arg("/")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("temporary"));
+
+    Ok(())
+}
+
+async fn test_missing_path_is_not_retried(_client: StorageClient) -> Result<()> {
+    let missing = format!("{}missing.txt", TEST_FIXTURE.new_dir_path());
+
+    let started = Instant::now();
+    storify_cmd()
+        .arg("--retries")
+        .arg("10")
+        .arg("stat")
+        .arg(&missing)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("NotFound"));
+
+    assert!(started.elapsed() < Duration::from_secs(1));
+    Ok(())
+}
+
+async fn test_profile_request_settings(client: StorageClient) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(client.operator());
+    client.operator().write(&path, content).await?;
+    let dir = std::env::temp_dir().join(format!("storify-cfg-{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).await?;
+    let config = dir.join("config.toml");
+    fs::write(
+        &config,
+        format!(
+            "[profiles.dev]\n{}retries = 5\ntimeout = 30\nio_timeout = 20\n",
+            minio_profile_toml()
+        ),
+    )
+    .await?;
+
+    bare_storify_cmd()
+        .arg("--config")
+        .arg(&config)
+        .arg("--profile")
+        .arg("dev")
+        .arg("stat")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("type=file"));
+
+    let loader = ConfigLoader::new(Some(&config), Some("dev"))?;
+    let requests = loader.load()?.requests;
+    assert_eq!(5, requests.retries);
+    assert_eq!(Duration::from_secs(30), requests.timeout);
+    assert_eq!(Duration::from_secs(20), requests.io_timeout);
+
+    // Settings given on the command line win over the profile.
+    let overrides = RequestOverrides {
+        retries: Some(1),
+        timeout: Some(90),
+        io_timeout: None,
+    };
+    let requests = loader.with_overrides(overrides).load()?.requests;
+    assert_eq!(1, requests.retries);
+    assert_eq!(Duration::from_secs(90), requests.timeout);
+    assert_eq!(Duration::from_secs(20), requests.io_timeout);
+
+    let _ = fs::remove_dir_all(&dir).await;
+    Ok(())
+}
+
+async fn test_rejects_zero_timeout(_client: StorageClient) -> Result<()> {
+    storify_cmd()
+        .arg("--timeout")
+        .arg("0")
+        .arg("ls")
+        .arg("/")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid value '0' for --timeout"));
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_sync_local_to_remote_is_incremental,
+        test_sync_transfers_changed_files,
+        test_sync_delete_extraneous,
+        test_sync_remote_to_local
+    ));
+}
+
+/// Create a local source tree with two files and return its root.
+async fn stage_local_tree() -> Result<PathBuf> {
+    let root = std::env::temp_dir().join(format!("storify-sync-{}", Uuid::new_v4()));
+    fs::create_dir_all(root.join("nested")).await?;
+    fs::write(root.join("a.txt"), b"alpha").await?;
+    fs::write(root.join("nested/b.txt"), b"beta").await?;
+    Ok(root)
+}
+
+fn fs_url(path: &Path) -> String {
+    format!("fs://{}/", path.display())
+}
+
+async fn test_sync_local_to_remote_is_incremental(client: StorageClient) -> Result<()> {
+    let local = stage_local_tree().await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("sync")
+        .arg(fs_url(&local))
+        .arg(&remote)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Sync complete: 2 copied, 0 skipped, 0 deleted",
+        ));
+
+    let b = client
+        .operator()
+        .read(&format!("{remote}nested/b.txt"))
+        .await?;
+    assert_eq!(b"beta".to_vec(), b.to_vec());
+
+    storify_cmd()
+        .arg("sync")
+        .arg(fs_url(&local))
+        .arg(&remote)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Sync complete: 0 copied, 2 skipped, 0 deleted",
+        ));
+
+    Ok(())
+}
+
+async fn test_sync_transfers_changed_files(client: StorageClient) -> Result<()> {
+    let local = stage_local_tree().await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("sync")
+        .arg(fs_url(&local))
+        .arg(&remote)
+        .assert()
+        .success();
+
+    fs::write(local.join("a.txt"), b"alpha, but longer").await?;
+
+    storify_cmd()
+        .arg("sync")
+        .arg(fs_url(&local))
+        .arg(&remote)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Sync complete: 1 copied, 1 skipped, 0 deleted",
+        ));
+
+    let a = client.operator().read(&format!("{remote}a.txt")).await?;
+    assert_eq!(b"alpha, but longer".to_vec(), a.to_vec());
+
+    Ok(())
+}
+
+async fn test_sync_delete_extraneous(client: StorageClient) -> Result<()> {
+    let local = stage_local_tree().await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+    let stale = format!("{remote}stale.txt");
+    client.operator().write(&stale, b"stale".to_vec()).await?;
+
+    storify_cmd()
+        .arg("sync")
+        .arg(fs_url(&local))
+        .arg(&remote)
+        .assert()
+        .success();
+    assert!(client.operator().exists(&stale).await?);
+
+    storify_cmd()
+        .arg("sync")
+        .arg("--delete")
+        .arg(fs_url(&local))
+        .arg(&remote)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 deleted"));
+    assert!(!client.operator().exists(&stale).await?);
+
+    Ok(())
+}
+
+async fn test_sync_remote_to_local(client: StorageClient) -> Result<()> {
+    let remote = TEST_FIXTURE.new_dir_path();
+    client
+        .operator()
+        .write(&format!("{remote}x.txt"), b"x".to_vec())
+        .await?;
+    client
+        .operator()
+        .write(&format!("{remote}sub/y.txt"), b"yy".to_vec())
+        .await?;
+    let local = std::env::temp_dir().join(format!("storify-sync-dst-{}", Uuid::new_v4()));
+
+    storify_cmd()
+        .arg("sync")
+        .arg(&remote)
+        .arg(fs_url(&local))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 copied"));
+
+    assert_eq!(b"x".to_vec(), fs::read(local.join("x.txt")).await?);
+    assert_eq!(b"yy".to_vec(), fs::read(local.join("sub/y.txt")).await?);
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::time::{Duration, Instant};
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+const MIB: usize = 1024 * 1024;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_put_limit_rate,
+        test_get_limit_rate_shared_by_jobs,
+        test_cat_limit_rate_from_profile,
+        test_rejects_invalid_limit_rate
+    ));
+}
+
+fn content(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+async fn test_put_limit_rate(client: StorageClient) -> Result<()> {
+    let local = std::env::temp_dir().join(format!("storify-rate-{}.bin", Uuid::new_v4()));
+    let data = content(3 * MIB);
+    fs::write(&local, &data).await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+    let file_name = local.file_name().unwrap().to_string_lossy().to_string();
+
+    let started = Instant::now();
+    storify_cmd()
+        .arg("--limit-rate")
+        .arg("1M")
+        .arg("put")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .success();
+
+    // One second worth of bytes passes at once; the other two are paced.
+    assert!(started.elapsed() >= Duration::from_millis(1800));
+    let uploaded = client
+        .operator()
+        .read(&join_remote_path(&remote, &file_name))
+        .await?;
+    assert_eq!(data, uploaded.to_vec());
+
+    let _ = fs::remove_file(&local).await;
+    Ok(())
+}
+
+async fn test_get_limit_rate_shared_by_jobs(client: StorageClient) -> Result<()> {
+    let remote = TEST_FIXTURE.new_dir_path();
+    let data = content(3 * MIB / 2);
+    for name in ["a.bin", "b.bin"] {
+        client
+            .operator()
+            .write(&format!("{remote}{name}"), data.clone())
+            .await?;
+    }
+    let local = std::env::temp_dir().join(format!("storify-rate-{}", Uuid::new_v4()));
+
+    let started = Instant::now();
+    storify_cmd()
+        .arg("--limit-rate")
+        .arg("1M")
+        .arg("get")
+        .arg("-j")
+        .arg("2")
+        .arg(&remote)
+        .arg(&local)
+        .assert()
+        .success();
+
+    // Limited per transfer, both files would be done within a second.
+    assert!(started.elapsed() >= Duration::from_millis(1800));
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_cat_limit_rate_from_profile(client: StorageClient) -> Result<()> {
+    let remote = format!("{}cat.bin", TEST_FIXTURE.new_dir_path());
+    client.operator().write(&remote, content(2 * MIB)).await?;
+    let dir = std::env::temp_dir().join(format!("storify-cfg-{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).await?;
+    let config = dir.join("config.toml");
+    fs::write(
+        &config,
+        format!(
+            "[profiles.dev]\n{}limit_rate = \"1M\"\n",
+            minio_profile_toml()
+        ),
+    )
+    .await?;
+
+    let started = Instant::now();
+    bare_storify_cmd()
+        .arg("--config")
+        .arg(&config)
+        .arg("--profile")
+        .arg("dev")
+        .arg("cat")
+        .arg(&remote)
+        .assert()
+        .success();
+
+    assert!(started.elapsed() >= Duration::from_millis(800));
+
+    let _ = fs::remove_dir_all(&dir).await;
+    Ok(())
+}
+
+async fn test_rejects_invalid_limit_rate(_client: StorageClient) -> Result<()> {
+    storify_cmd()
+        .arg("--limit-rate")
+        .arg("fast")
+        .arg("ls")
+        .arg("/")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid value 'fast' for --limit-rate",
+        ));
+
+    Ok(())
+}
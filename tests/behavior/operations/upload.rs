@@ -5,6 +5,7 @@ use predicates::prelude::*;
 use storify::error::Result;
 use storify::storage::StorageClient;
 use tokio::fs;
+use uuid::Uuid;
 
 pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
     tests.extend(async_trials!(
@@ -14,6 +15,18 @@ pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
     ));
 
     tests.extend(async_trials!(client, e2e_test_upload_command_succeeds));
+
+    tests.extend(async_trials!(
+        client,
+        test_upload_directory_in_parallel,
+        test_upload_rejects_zero_jobs,
+        test_upload_large_file_in_concurrent_parts,
+        test_upload_rejects_small_part_size,
+        test_upload_resume_appends_to_partial_object,
+        test_upload_resume_warns_without_append_support,
+        test_upload_recursive_skips_partial_downloads,
+        test_upload_verify_multipart_object
+    ));
 }
 
 async fn test_storage_client_write(_client: StorageClient) -> Result<()> {
@@ -87,3 +100,241 @@ async fn e2e_test_upload_command_succeeds(_client: StorageClient) -> Result<()>
 
     Ok(())
 }
+
+async fn test_upload_directory_in_parallel(client: StorageClient) -> Result<()> {
+    let local = std::env::temp_dir().join(format!("storify-put-jobs-{}", Uuid::new_v4()));
+    fs::create_dir_all(local.join("nested")).await?;
+    for i in 0..6 {
+        fs::write(local.join(format!("file_{i}.txt")), format!("content {i}")).await?;
+    }
+    fs::write(local.join("nested/deep.txt"), b"deep").await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("put")
+        .arg("-R")
+        .arg("-j")
+        .arg("4")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .success();
+
+    for i in 0..6 {
+        let content = client
+            .operator()
+            .read(&format!("{remote}file_{i}.txt"))
+            .await?;
+        assert_eq!(format!("content {i}").into_bytes(), content.to_vec());
+    }
+    let deep = client
+        .operator()
+        .read(&format!("{remote}nested/deep.txt"))
+        .await?;
+    assert_eq!(b"deep".to_vec(), deep.to_vec());
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_upload_rejects_zero_jobs(_client: StorageClient) -> Result<()> {
+    let source_path = get_test_data_path("small.txt");
+    let dest_path = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("put")
+        .arg("--jobs")
+        .arg("0")
+        .arg(&source_path)
+        .arg(&dest_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid value '0' for --jobs"));
+
+    Ok(())
+}
+
+async fn test_upload_large_file_in_concurrent_parts(client: StorageClient) -> Result<()> {
+    let local = std::env::temp_dir().join(format!("storify-put-parts-{}.bin", Uuid::new_v4()));
+    let content: Vec<u8> = (0..12 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+    fs::write(&local, &content).await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+    let file_name = local.file_name().unwrap().to_string_lossy().to_string();
+
+    storify_cmd()
+        .arg("put")
+        .arg("--part-size")
+        .arg("5M")
+        .arg("--concurrency")
+        .arg("3")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("100%").not())
+        .stdout(predicate::str::contains(format!(
+            "({} bytes)",
+            content.len()
+        )));
+
+    let uploaded = client
+        .operator()
+        .read(&join_remote_path(&remote, &file_name))
+        .await?;
+    assert_eq!(content, uploaded.to_vec());
+
+    let _ = fs::remove_file(&local).await;
+    Ok(())
+}
+
+async fn test_upload_rejects_small_part_size(_client: StorageClient) -> Result<()> {
+    let source_path = get_test_data_path("small.txt");
+    let dest_path = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("put")
+        .arg("--part-size")
+        .arg("1K")
+        .arg(&source_path)
+        .arg(&dest_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid value '1K' for --part-size",
+        ));
+
+    Ok(())
+}
+
+async fn test_upload_resume_appends_to_partial_object(_client: StorageClient) -> Result<()> {
+    let root = std::env::temp_dir().join(format!("storify-put-resume-{}", Uuid::new_v4()));
+    let local_dir = root.join("src");
+    let remote_dir = root.join("dest");
+    let state_home = root.join("state");
+    fs::create_dir_all(&local_dir).await?;
+    fs::create_dir_all(&remote_dir).await?;
+
+    let local_file = local_dir.join("resume.bin");
+    let content: Vec<u8> = (0..64 * 1024).map(|i| (i % 251) as u8).collect();
+    fs::write(&local_file, &content).await?;
+    // The partial object holds different bytes, so a restart would be detected.
+    fs::write(remote_dir.join("resume.bin"), vec![0xFF; 1000]).await?;
+
+    let meta = std::fs::metadata(&local_file)?;
+    let state = serde_json::json!({
+        "remote_path": remote_dir.join("resume.bin").to_string_lossy(),
+        "size": meta.len(),
+        "etag": null,
+        "last_modified": meta
+            .modified()
+            .ok()
+            .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339()),
+    });
+    let state_path = resume_state_path(&state_home, &local_file);
+    fs::create_dir_all(state_path.parent().unwrap()).await?;
+    fs::write(&state_path, serde_json::to_vec(&state)?).await?;
+
+    storify_cmd()
+        .env("XDG_STATE_HOME", &state_home)
+        .arg("put")
+        .arg("--resume")
+        .arg(&local_file)
+        .arg(format!("fs://{}/", remote_dir.display()))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("from byte 1000"));
+
+    let uploaded = fs::read(remote_dir.join("resume.bin")).await?;
+    assert_eq!(content.len(), uploaded.len());
+    assert_eq!(vec![0xFF; 1000], uploaded[..1000]);
+    assert_eq!(content[1000..], uploaded[1000..]);
+    assert!(!state_path.exists());
+    assert_eq!(1, std::fs::read_dir(&local_dir)?.count());
+
+    let _ = fs::remove_dir_all(&root).await;
+    Ok(())
+}
+
+async fn test_upload_resume_warns_without_append_support(client: StorageClient) -> Result<()> {
+    let source_path = get_test_data_path("small.txt");
+    let dest_path = TEST_FIXTURE.new_dir_path();
+    let expected_content = fs::read(&source_path).await?;
+
+    storify_cmd()
+        .arg("put")
+        .arg("--resume")
+        .arg(&source_path)
+        .arg(&dest_path)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "does not support resumable uploads",
+        ));
+
+    let uploaded = client
+        .operator()
+        .read(&join_remote_path(&dest_path, "small.txt"))
+        .await?;
+    assert_eq!(expected_content, uploaded.to_vec());
+    Ok(())
+}
+
+async fn test_upload_recursive_skips_partial_downloads(client: StorageClient) -> Result<()> {
+    let local = std::env::temp_dir().join(format!("storify-put-partial-{}", Uuid::new_v4()));
+    fs::create_dir_all(&local).await?;
+    fs::write(local.join("a.txt"), b"alpha").await?;
+    fs::write(local.join("b.txt.storify-part"), b"interrupted").await?;
+    let dest_path = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("put")
+        .arg("-R")
+        .arg(&local)
+        .arg(&dest_path)
+        .assert()
+        .success();
+
+    assert!(
+        client
+            .operator()
+            .exists(&format!("{dest_path}a.txt"))
+            .await?
+    );
+    assert!(
+        !client
+            .operator()
+            .exists(&format!("{dest_path}b.txt.storify-part"))
+            .await?
+    );
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_upload_verify_multipart_object(client: StorageClient) -> Result<()> {
+    let local = std::env::temp_dir().join(format!("storify-put-verify-{}.bin", Uuid::new_v4()));
+    let content: Vec<u8> = (0..11 * 1024 * 1024).map(|i| (i % 253) as u8).collect();
+    fs::write(&local, &content).await?;
+    let remote = TEST_FIXTURE.new_dir_path();
+    let file_name = local.file_name().unwrap().to_string_lossy().to_string();
+
+    // Multipart ETags are not plain MD5 digests, so the stored object is re-read
+    storify_cmd()
+        .arg("put")
+        .arg("--verify")
+        .arg("--part-size")
+        .arg("5M")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .success();
+
+    let uploaded = client
+        .operator()
+        .read(&join_remote_path(&remote, &file_name))
+        .await?;
+    assert_eq!(content, uploaded.to_vec());
+
+    let _ = fs::remove_file(&local).await;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::PathBuf;
+use storify::error::Result;
+use storify::storage::StorageClient;
+use tokio::fs;
+use uuid::Uuid;
+
+pub fn tests(client: &StorageClient, tests: &mut Vec<Trial>) {
+    tests.extend(async_trials!(
+        client,
+        test_verify_matching_tree,
+        test_verify_reports_mismatches_as_json,
+        test_verify_checksum_detects_same_size_change,
+        test_verify_missing_local_directory
+    ));
+}
+
+/// Create a local tree with `a.txt` and `nested/b.txt`, mirrored under a new remote prefix.
+async fn mirrored_tree(client: &StorageClient) -> Result<(PathBuf, String)> {
+    let local = std::env::temp_dir().join(format!("storify-verify-{}", Uuid::new_v4()));
+    fs::create_dir_all(local.join("nested")).await?;
+    fs::write(local.join("a.txt"), b"alpha").await?;
+    fs::write(local.join("nested/b.txt"), b"beta").await?;
+
+    let remote = TEST_FIXTURE.new_dir_path();
+    client
+        .operator()
+        .write(&format!("{remote}a.txt"), b"alpha".to_vec())
+        .await?;
+    client
+        .operator()
+        .write(&format!("{remote}nested/b.txt"), b"beta".to_vec())
+        .await?;
+    Ok((local, remote))
+}
+
+async fn test_verify_matching_tree(client: StorageClient) -> Result<()> {
+    let (local, remote) = mirrored_tree(&client).await?;
+
+    storify_cmd()
+        .arg("verify")
+        .arg("--checksum")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Verify: 2 matched, 0 missing, 0 extra, 0 differing",
+        ));
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_verify_reports_mismatches_as_json(client: StorageClient) -> Result<()> {
+    let (local, remote) = mirrored_tree(&client).await?;
+    fs::write(local.join("only-local.txt"), b"local").await?;
+    fs::write(local.join("a.txt"), b"alpha, longer").await?;
+    client
+        .operator()
+        .write(&format!("{remote}only-remote.txt"), b"remote".to_vec())
+        .await?;
+
+    let output = storify_cmd()
+        .arg("verify")
+        .arg("--json")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1 missing, 1 extra, 1 differing"))
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(report["matched"], 1);
+    assert_eq!(report["missing"], serde_json::json!(["only-local.txt"]));
+    assert_eq!(report["extra"], serde_json::json!(["only-remote.txt"]));
+    assert_eq!(report["differing"][0]["path"], "a.txt");
+    assert_eq!(report["differing"][0]["field"], "size");
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_verify_checksum_detects_same_size_change(client: StorageClient) -> Result<()> {
+    let (local, remote) = mirrored_tree(&client).await?;
+    fs::write(local.join("a.txt"), b"ALPHA").await?;
+
+    storify_cmd()
+        .arg("verify")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .success();
+
+    storify_cmd()
+        .arg("verify")
+        .arg("--checksum")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Differs: a.txt (md5"));
+
+    let _ = fs::remove_dir_all(&local).await;
+    Ok(())
+}
+
+async fn test_verify_missing_local_directory(_client: StorageClient) -> Result<()> {
+    let local = std::env::temp_dir().join(format!("storify-verify-{}", Uuid::new_v4()));
+    let remote = TEST_FIXTURE.new_dir_path();
+
+    storify_cmd()
+        .arg("verify")
+        .arg(&local)
+        .arg(&remote)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to verify"));
+
+    Ok(())
+}
@@ -3,8 +3,9 @@ use libtest_mimic::{Failed, Trial};
 use opendal::Operator;
 use rand::Rng;
 use rand::prelude::*;
+use sha2::{Digest, Sha256};
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::LazyLock;
 use storify::error::Result;
@@ -111,6 +112,18 @@ pub async fn ensure_bucket_exists(op: &Operator) -> Result<()> {
     }
 }
 
+/// Path of the resume state storify keeps for `local_path` when `XDG_STATE_HOME` is
+/// `state_home`: named after the SHA-256 of the absolute local path.
+pub fn resume_state_path(state_home: &Path, local_path: &Path) -> PathBuf {
+    let absolute = std::path::absolute(local_path).unwrap();
+    let digest = Sha256::digest(absolute.as_os_str().as_encoded_bytes());
+    let name: String = digest.iter().map(|b| format!("{b:02x}")).collect();
+    state_home
+        .join("storify")
+        .join("resume")
+        .join(format!("{name}.json"))
+}
+
 pub fn join_remote_path(remote_path: &str, file_name: &str) -> String {
     if remote_path.ends_with('/') {
         format!("{remote_path}{file_name}")
@@ -195,7 +208,7 @@ pub struct E2eTestEnv {
 impl E2eTestEnv {
     pub async fn new() -> Self {
         let cfg = TEST_MINIO_CONFIG.clone();
-        let verifier = StorageClient::new(cfg.clone())
+        let verifier = StorageClient::quiet(cfg.clone())
             .await
             .expect("failed to create verifier client");
         ensure_bucket_exists(verifier.operator())
@@ -240,6 +253,28 @@ macro_rules! async_trials {
 
 pub static TEST_FIXTURE: Fixture = Fixture::new();
 
+/// Returns a storify Command without any storage environment variables applied.
+pub fn bare_storify_cmd() -> Command {
+    base_cmd()
+}
+
+/// Render the MinIO test config as a config file profile body (without the table header).
+pub fn minio_profile_toml() -> String {
+    let cfg = TEST_MINIO_CONFIG.clone();
+    format!(
+        "provider = \"minio\"\nbucket = \"{}\"\nendpoint = \"{}\"\naccess_key_id = \"{}\"\naccess_key_secret = \"{}\"\nregion = \"{}\"\n",
+        cfg.bucket,
+        cfg.endpoint.as_deref().unwrap_or(TEST_DEFAULT_ENDPOINT),
+        cfg.access_key_id
+            .as_deref()
+            .unwrap_or(TEST_DEFAULT_ACCESS_KEY_ID),
+        cfg.access_key_secret
+            .as_deref()
+            .unwrap_or(TEST_DEFAULT_ACCESS_KEY_SECRET),
+        cfg.region.as_deref().unwrap_or(TEST_DEFAULT_REGION),
+    )
+}
+
 pub fn storify_cmd() -> Command {
     let cfg = TEST_MINIO_CONFIG.clone();
     let mut cmd = base_cmd();